@@ -1,97 +1,260 @@
-//! Search command - Search for text patterns
+//! Search command - regex-powered text search over files and archive members
+//!
+//! Replaces the original case-folded `contains` scan with a real regex
+//! engine (`regex` crate) so patterns can express word boundaries,
+//! alternation, and anchors, plus optional context lines and a structured
+//! JSON output mode for tooling.
 
 use anyhow::{Context, Result};
 use engram_rs::ArchiveReader;
-use std::fs::read_to_string;
+use regex::{Regex, RegexBuilder};
 use std::io::Write;
 use std::path::Path;
 
-pub fn search(
-    pattern: &str,
-    path: &Path,
-    in_archive: bool,
-    case_insensitive: bool,
-) -> Result<()> {
+/// Flags controlling how a pattern is matched and how much context is shown.
+#[derive(Debug, Clone, Default)]
+pub struct SearchOptions {
+    pub case_insensitive: bool,
+    pub whole_word: bool,
+    pub fixed_strings: bool,
+    pub multiline: bool,
+    pub before: usize,
+    pub after: usize,
+    pub max_count: Option<usize>,
+    pub json: bool,
+}
+
+/// A single matching line: its number, text, the byte span(s) of every match
+/// on it, and any requested context lines.
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    pub line_number: usize,
+    pub line: String,
+    pub spans: Vec<(usize, usize)>,
+    pub before: Vec<String>,
+    pub after: Vec<String>,
+}
+
+/// Hits grouped under the file they came from (`None` for a plain on-disk
+/// file rather than an archive member).
+#[derive(Debug, Clone)]
+pub struct FileMatches {
+    pub file_path: Option<String>,
+    pub hits: Vec<SearchHit>,
+}
+
+pub fn search(pattern: &str, path: &Path, in_archive: bool, opts: &SearchOptions) -> Result<()> {
     if in_archive {
-        // Search inside archive
-        search_in_archive(pattern, path, case_insensitive)
+        search_in_archive(pattern, path, opts)
     } else {
-        // Search regular file
-        search_in_file(pattern, path, case_insensitive)
+        search_in_file(pattern, path, opts)
     }
 }
 
-fn search_in_file(pattern: &str, path: &Path, case_insensitive: bool) -> Result<()> {
-    let content = read_to_string(path)
+fn build_regex(pattern: &str, opts: &SearchOptions) -> Result<Regex> {
+    let pattern = if opts.fixed_strings {
+        regex::escape(pattern)
+    } else {
+        pattern.to_string()
+    };
+    let pattern = if opts.whole_word {
+        format!(r"\b(?:{})\b", pattern)
+    } else {
+        pattern
+    };
+
+    RegexBuilder::new(&pattern)
+        .case_insensitive(opts.case_insensitive)
+        .multi_line(opts.multiline)
+        .dot_matches_new_line(opts.multiline)
+        .build()
+        .with_context(|| format!("Invalid search pattern `{}`", pattern))
+}
+
+/// Find every regex match in `content`, grouped by the line it starts on,
+/// with byte spans relative to that line and any requested context lines.
+pub fn find_matches(content: &str, pattern: &str, opts: &SearchOptions) -> Result<Vec<SearchHit>> {
+    let regex = build_regex(pattern, opts)?;
+    let lines: Vec<&str> = content.lines().collect();
+
+    let mut line_starts = Vec::with_capacity(lines.len());
+    let mut offset = 0usize;
+    for line in &lines {
+        line_starts.push(offset);
+        offset += line.len() + 1;
+    }
+
+    let mut hits: Vec<SearchHit> = Vec::new();
+
+    for m in regex.find_iter(content) {
+        if opts.max_count.is_some_and(|max| hits.len() >= max) {
+            break;
+        }
+
+        let line_idx = match line_starts.binary_search(&m.start()) {
+            Ok(i) => i,
+            Err(i) => i.saturating_sub(1),
+        };
+        let Some(&line_start) = line_starts.get(line_idx) else {
+            continue;
+        };
+        let line = lines[line_idx];
+        let span = (
+            m.start().saturating_sub(line_start),
+            m.end().saturating_sub(line_start).min(line.len()),
+        );
+
+        if let Some(last) = hits.last_mut() {
+            if last.line_number == line_idx + 1 {
+                last.spans.push(span);
+                continue;
+            }
+        }
+
+        let before_start = line_idx.saturating_sub(opts.before);
+        let after_end = (line_idx + 1 + opts.after).min(lines.len());
+
+        hits.push(SearchHit {
+            line_number: line_idx + 1,
+            line: line.to_string(),
+            spans: vec![span],
+            before: lines[before_start..line_idx]
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            after: lines[line_idx + 1..after_end]
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+        });
+    }
+
+    Ok(hits)
+}
+
+fn search_in_file(pattern: &str, path: &Path, opts: &SearchOptions) -> Result<()> {
+    let content = std::fs::read_to_string(path)
         .with_context(|| format!("Could not read file `{}`", path.display()))?;
 
-    find_matches(&content, pattern, &mut std::io::stdout(), case_insensitive)
+    let hits = find_matches(&content, pattern, opts)
         .with_context(|| format!("Failed to find matching content for pattern `{}`", pattern))?;
 
+    let mut stdout = std::io::stdout();
+    if hits.is_empty() {
+        println!("No matches found");
+    } else {
+        print_matches(&FileMatches { file_path: None, hits }, opts, &mut stdout)?;
+    }
+
     Ok(())
 }
 
-fn search_in_archive(pattern: &str, archive_path: &Path, case_insensitive: bool) -> Result<()> {
+/// Search every file in the archive, fanning the scan out across a small
+/// worker pool so large archives with many members search quickly.
+fn search_in_archive(pattern: &str, archive_path: &Path, opts: &SearchOptions) -> Result<()> {
     let mut reader = ArchiveReader::open(archive_path)?;
     reader.initialize()?;
 
-    let mut found_any = false;
-
-    // Clone the files list to avoid borrowing issues
     let all_files = reader.list_files().to_vec();
+    if all_files.is_empty() {
+        println!("No matches found");
+        return Ok(());
+    }
 
-    for file_path in &all_files {
-        // Try to read as text
-        if let Ok(data) = reader.read_file(file_path)
-            && let Ok(content) = String::from_utf8(data)
-        {
-            let mut matches = Vec::new();
-
-            for line in content.lines() {
-                let matches_line = if case_insensitive {
-                    line.to_lowercase().contains(&pattern.to_lowercase())
-                } else {
-                    line.contains(pattern)
-                };
-
-                if matches_line {
-                    matches.push(line.to_string());
-                }
-            }
+    let worker_count = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+        .min(all_files.len());
+    let chunk_size = all_files.len().div_ceil(worker_count).max(1);
 
-            if !matches.is_empty() {
-                println!("\n{}:", file_path);
-                for line in matches {
-                    println!("  {}", line);
-                }
-                found_any = true;
-            }
-        }
-    }
+    // ArchiveReader isn't Sync, so each worker re-opens the archive rather
+    // than sharing one reader across threads.
+    let results: Vec<Result<FileMatches>> = std::thread::scope(|scope| {
+        let handles: Vec<_> = all_files
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || -> Result<Vec<FileMatches>> {
+                    let mut reader = ArchiveReader::open(archive_path)?;
+                    reader.initialize()?;
 
-    if !found_any {
+                    let mut out = Vec::new();
+                    for file_path in chunk {
+                        let Ok(data) = reader.read_file(file_path) else {
+                            continue;
+                        };
+                        let Ok(content) = String::from_utf8(data) else {
+                            continue;
+                        };
+
+                        let hits = find_matches(&content, pattern, opts)?;
+                        if !hits.is_empty() {
+                            out.push(FileMatches {
+                                file_path: Some(file_path.clone()),
+                                hits,
+                            });
+                        }
+                    }
+                    Ok(out)
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|h| h.join().unwrap_or_else(|_| Err(anyhow::anyhow!("search worker panicked"))))
+            .flat_map(|r| match r {
+                Ok(v) => v.into_iter().map(Ok).collect::<Vec<_>>(),
+                Err(e) => vec![Err(e)],
+            })
+            .collect()
+    });
+
+    if results.is_empty() {
         println!("No matches found");
+        return Ok(());
+    }
+
+    let mut stdout = std::io::stdout();
+    for result in results {
+        print_matches(&result?, opts, &mut stdout)?;
     }
 
     Ok(())
 }
 
-fn find_matches(
-    content: &str,
-    pattern: &str,
-    mut writer: impl Write,
-    case_insensitive: bool,
-) -> Result<()> {
-    for line in content.lines() {
-        let matches = if case_insensitive {
-            line.to_lowercase().contains(&pattern.to_lowercase())
-        } else {
-            line.contains(pattern)
-        };
+fn print_matches(file_matches: &FileMatches, opts: &SearchOptions, writer: &mut impl Write) -> Result<()> {
+    if opts.json {
+        let value = serde_json::json!({
+            "file": file_matches.file_path,
+            "matches": file_matches.hits.iter().map(|h| serde_json::json!({
+                "line": h.line_number,
+                "content": h.line,
+                "spans": h.spans.iter().map(|(start, end)| serde_json::json!({
+                    "start": start,
+                    "end": end,
+                })).collect::<Vec<_>>(),
+                "before": h.before,
+                "after": h.after,
+            })).collect::<Vec<_>>(),
+        });
 
-        if matches {
-            writeln!(writer, "{}", line)?;
+        writeln!(writer, "{}", serde_json::to_string(&value)?)?;
+        return Ok(());
+    }
+
+    if let Some(path) = &file_matches.file_path {
+        writeln!(writer, "\n{}:", path)?;
+    }
+
+    for hit in &file_matches.hits {
+        for line in &hit.before {
+            writeln!(writer, "  {}", line)?;
+        }
+        writeln!(writer, "{}: {}", hit.line_number, hit.line)?;
+        for line in &hit.after {
+            writeln!(writer, "  {}", line)?;
         }
     }
+
     Ok(())
 }
@@ -0,0 +1,126 @@
+//! POSIX metadata capture/restore for `pack --preserve`/`extract --preserve`
+//!
+//! Engram archives have no native concept of symlinks, directories, or unix
+//! permissions — only named file content — so `pack --preserve` walks the
+//! source tree with [`std::fs::symlink_metadata`] semantics (via
+//! `WalkDir::follow_links(false)`) and records every entry's node kind,
+//! mode, ownership, and mtime into a top-level [`FS_METADATA_FILE`]
+//! sidecar. `extract --preserve` reads that sidecar back to recreate
+//! symlinks and restore mode/ownership/mtime on the files it writes. This
+//! is unix-only, same as the rest of this crate.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::{symlink, MetadataExt, PermissionsExt};
+use std::path::Path;
+
+/// Name of the sidecar `pack --preserve` writes into the archive, and that
+/// `extract --preserve` consults to recreate symlinks and restore
+/// mode/ownership/mtime
+pub const FS_METADATA_FILE: &str = "_fs_metadata.json";
+
+/// One archive entry's captured POSIX metadata, as stored in
+/// [`FS_METADATA_FILE`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntryMetadata {
+    pub kind: EntryKind,
+    pub mode: u32,
+    pub uid: u32,
+    pub gid: u32,
+    pub mtime: i64,
+}
+
+/// The node type half of [`EntryMetadata`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum EntryKind {
+    Regular,
+    Directory,
+    Symlink { target: String },
+}
+
+/// Classify a filesystem node and capture the metadata needed to recreate
+/// it faithfully: its node type, mode, ownership, and mtime.
+///
+/// `meta` must come from `symlink_metadata` (or a `WalkDir` entry walked
+/// with `follow_links(false)`), so that symlinks are classified as links
+/// rather than followed.
+pub fn capture(path: &Path, meta: &std::fs::Metadata) -> Result<EntryMetadata> {
+    let file_type = meta.file_type();
+    let kind = if file_type.is_symlink() {
+        let target = std::fs::read_link(path)
+            .with_context(|| format!("Failed to read symlink target for {}", path.display()))?;
+        let target = target
+            .to_str()
+            .with_context(|| format!("Symlink target for {} is not valid UTF-8", path.display()))?
+            .to_string();
+        EntryKind::Symlink { target }
+    } else if file_type.is_dir() {
+        EntryKind::Directory
+    } else {
+        EntryKind::Regular
+    };
+
+    Ok(EntryMetadata {
+        kind,
+        mode: meta.mode(),
+        uid: meta.uid(),
+        gid: meta.gid(),
+        mtime: meta.mtime(),
+    })
+}
+
+/// Recreate `entry`'s node kind (if it doesn't already exist, e.g. a
+/// symlink or directory with no archive content of its own) and restore
+/// its mode, ownership, and mtime.
+///
+/// Symlinks have no mode of their own on Linux (there is no `lchmod`), so
+/// that step is skipped for them; ownership restoration is best-effort
+/// since it requires privileges the caller may not have.
+pub fn apply(path: &Path, entry: &EntryMetadata) -> Result<()> {
+    if let EntryKind::Symlink { target } = &entry.kind {
+        if !path.exists() {
+            symlink(target, path)
+                .with_context(|| format!("Failed to create symlink {}", path.display()))?;
+        }
+    }
+
+    let is_symlink = matches!(entry.kind, EntryKind::Symlink { .. });
+    if !is_symlink {
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(entry.mode))
+            .with_context(|| format!("Failed to set mode on {}", path.display()))?;
+    }
+
+    let c_path = std::ffi::CString::new(path.as_os_str().as_bytes())
+        .with_context(|| format!("Path contains a NUL byte: {}", path.display()))?;
+    let ret = unsafe { libc::lchown(c_path.as_ptr(), entry.uid as libc::uid_t, entry.gid as libc::gid_t) };
+    if ret != 0 {
+        eprintln!(
+            "Warning: failed to chown {}: {}",
+            path.display(),
+            std::io::Error::last_os_error()
+        );
+    }
+
+    if !is_symlink {
+        let times = libc::timespec {
+            tv_sec: entry.mtime,
+            tv_nsec: 0,
+        };
+        let specs = [
+            libc::timespec { tv_sec: 0, tv_nsec: libc::UTIME_OMIT },
+            times,
+        ];
+        let ret = unsafe { libc::utimensat(libc::AT_FDCWD, c_path.as_ptr(), specs.as_ptr(), 0) };
+        if ret != 0 {
+            eprintln!(
+                "Warning: failed to set mtime on {}: {}",
+                path.display(),
+                std::io::Error::last_os_error()
+            );
+        }
+    }
+
+    Ok(())
+}
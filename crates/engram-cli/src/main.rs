@@ -1,10 +1,14 @@
 use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
-use engram_core::{ArchiveReader, ArchiveWriter};
+use engram_core::{ArchiveReader, ArchiveWriter, CompressionMethod};
+use std::collections::BTreeMap;
 use std::fs::{self, read_to_string};
 use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
+mod fs_meta;
+use fs_meta::{EntryKind, EntryMetadata, FS_METADATA_FILE};
+
 #[derive(Parser)]
 #[command(name = "engram")]
 #[command(about = "A CLI tool for managing Engram archives", long_about = None)]
@@ -42,6 +46,36 @@ enum Commands {
         /// Path for the output archive (defaults to input name with .engram extension)
         #[arg(short, long)]
         output: Option<PathBuf>,
+
+        /// Capture symlinks and each entry's unix mode, ownership, and
+        /// mtime, so `extract` can recreate the tree faithfully instead of
+        /// just its regular files
+        #[arg(long, default_value_t = true)]
+        preserve: bool,
+
+        /// Shorthand for --preserve=false
+        #[arg(long)]
+        no_preserve: bool,
+    },
+
+    /// Extracts files from an Engram archive into a directory
+    #[command(alias = "x")]
+    Extract {
+        /// Path to the Engram archive file
+        path: PathBuf,
+
+        /// Directory to extract files into
+        #[arg(short, long)]
+        output: PathBuf,
+
+        /// Restore symlinks and unix mode/ownership/mtime captured by
+        /// `pack --preserve`
+        #[arg(long, default_value_t = true)]
+        preserve: bool,
+
+        /// Shorthand for --preserve=false
+        #[arg(long)]
+        no_preserve: bool,
     },
 
     /// Searches for a text pattern within a file and prints matching lines
@@ -66,8 +100,12 @@ fn main() -> Result<()> {
             show_archive_info(&path, inspect)?;
         }
 
-        Commands::Pack { path, output } => {
-            pack_archive(&path, output.as_deref())?;
+        Commands::Pack { path, output, preserve, no_preserve } => {
+            pack_archive(&path, output.as_deref(), preserve && !no_preserve)?;
+        }
+
+        Commands::Extract { path, output, preserve, no_preserve } => {
+            extract_archive(&path, &output, preserve && !no_preserve)?;
         }
 
         Commands::Search { pattern, path } => {
@@ -192,8 +230,14 @@ fn show_archive_info(archive_path: &Path, inspect: bool) -> Result<()> {
     Ok(())
 }
 
-/// Packs files or directories into a new Engram archive
-fn pack_archive(source_path: &Path, output_path: Option<&Path>) -> Result<()> {
+/// Packs files or directories into a new Engram archive.
+///
+/// With `preserve` set, the directory walk also records symlinks and each
+/// entry's mode, ownership, and mtime into a top-level [`FS_METADATA_FILE`]
+/// sidecar (see [`fs_meta`]) instead of silently dropping everything but
+/// regular files; `extract_archive` consults that sidecar to recreate the
+/// tree faithfully.
+fn pack_archive(source_path: &Path, output_path: Option<&Path>, preserve: bool) -> Result<()> {
     // Determine output path
     let output = match output_path {
         Some(p) => p.to_path_buf(),
@@ -213,6 +257,8 @@ fn pack_archive(source_path: &Path, output_path: Option<&Path>) -> Result<()> {
     let metadata = fs::metadata(source_path)
         .with_context(|| format!("failed to read metadata for `{}`", source_path.display()))?;
 
+    let mut fs_metadata: BTreeMap<String, EntryMetadata> = BTreeMap::new();
+
     if metadata.is_file() {
         // Pack a single file
         let file_name = source_path
@@ -220,6 +266,10 @@ fn pack_archive(source_path: &Path, output_path: Option<&Path>) -> Result<()> {
             .and_then(|n| n.to_str())
             .context("invalid file name")?;
 
+        if preserve {
+            fs_metadata.insert(file_name.to_string(), fs_meta::capture(source_path, &metadata)?);
+        }
+
         writer
             .add_file_from_disk(file_name, source_path)
             .with_context(|| format!("failed to add file `{}`", source_path.display()))?;
@@ -237,21 +287,45 @@ fn pack_archive(source_path: &Path, output_path: Option<&Path>) -> Result<()> {
                 format!("failed to read directory entry in `{}`", source_path.display())
             })?;
 
-            if entry.file_type().is_file() {
-                // Get relative path and normalize separators
-                let relative_path = entry
-                    .path()
-                    .strip_prefix(source_path)
-                    .with_context(|| {
-                        format!(
-                            "failed to get relative path for `{}`",
-                            entry.path().display()
-                        )
-                    })?
-                    .to_str()
-                    .context("invalid file path")?
-                    .replace('\\', "/");
+            if entry.depth() == 0 {
+                // The root directory itself has no archive path of its own
+                continue;
+            }
 
+            // Get relative path and normalize separators
+            let relative_path = entry
+                .path()
+                .strip_prefix(source_path)
+                .with_context(|| {
+                    format!(
+                        "failed to get relative path for `{}`",
+                        entry.path().display()
+                    )
+                })?
+                .to_str()
+                .context("invalid file path")?
+                .replace('\\', "/");
+
+            if !preserve {
+                if entry.file_type().is_file() {
+                    writer
+                        .add_file_from_disk(&relative_path, entry.path())
+                        .with_context(|| format!("failed to add file `{}`", entry.path().display()))?;
+
+                    println!("  Added: {}", relative_path);
+                    file_count += 1;
+                }
+                continue;
+            }
+
+            let entry_metadata = entry.metadata().with_context(|| {
+                format!("failed to read metadata for `{}`", entry.path().display())
+            })?;
+
+            // Only regular files carry content in the archive; directories
+            // and symlinks are fully described by their `_fs_metadata.json`
+            // entry
+            if entry_metadata.is_file() {
                 writer
                     .add_file_from_disk(&relative_path, entry.path())
                     .with_context(|| format!("failed to add file `{}`", entry.path().display()))?;
@@ -259,6 +333,8 @@ fn pack_archive(source_path: &Path, output_path: Option<&Path>) -> Result<()> {
                 println!("  Added: {}", relative_path);
                 file_count += 1;
             }
+
+            fs_metadata.insert(relative_path, fs_meta::capture(entry.path(), &entry_metadata)?);
         }
 
         println!("Packed {} files", file_count);
@@ -266,6 +342,12 @@ fn pack_archive(source_path: &Path, output_path: Option<&Path>) -> Result<()> {
         anyhow::bail!("path is neither a file nor a directory: {}", source_path.display());
     }
 
+    if preserve && !fs_metadata.is_empty() {
+        let metadata_json = serde_json::to_vec_pretty(&fs_metadata)?;
+        writer.add_file_with_compression(FS_METADATA_FILE, &metadata_json, CompressionMethod::None)?;
+        println!("  Added: {} ({} entries)", FS_METADATA_FILE, fs_metadata.len());
+    }
+
     // Finalize the archive (writes central directory and updates header)
     writer
         .finalize()
@@ -273,5 +355,85 @@ fn pack_archive(source_path: &Path, output_path: Option<&Path>) -> Result<()> {
 
     println!("Archive created successfully: {}", output.display());
 
+    Ok(())
+}
+
+/// Extracts every file in an Engram archive into `output_dir`.
+///
+/// With `preserve` set, a `_fs_metadata.json` sidecar written by
+/// `pack_archive --preserve` (see [`fs_meta`]) is consulted to recreate
+/// symlinks and restore each file's mode, ownership, and mtime.
+fn extract_archive(archive_path: &Path, output_dir: &Path, preserve: bool) -> Result<()> {
+    let mut reader = ArchiveReader::open(archive_path)
+        .with_context(|| format!("failed to open archive `{}`", archive_path.display()))?;
+
+    fs::create_dir_all(output_dir)
+        .with_context(|| format!("failed to create output directory `{}`", output_dir.display()))?;
+
+    let fs_metadata: BTreeMap<String, EntryMetadata> =
+        if preserve && reader.list_files().iter().any(|f| f == FS_METADATA_FILE) {
+            let data = reader
+                .read_file(FS_METADATA_FILE)
+                .context("failed to read _fs_metadata.json sidecar")?;
+            serde_json::from_slice(&data).context("failed to parse _fs_metadata.json sidecar")?
+        } else {
+            BTreeMap::new()
+        };
+
+    println!("Extracting to: {}", output_dir.display());
+
+    let files: Vec<String> = reader.list_files().to_vec();
+
+    let mut file_count = 0;
+    for file_path in &files {
+        if file_path == FS_METADATA_FILE {
+            continue;
+        }
+
+        let data = reader
+            .read_file(file_path)
+            .with_context(|| format!("failed to read file `{}` from archive", file_path))?;
+
+        let output_path = output_dir.join(file_path);
+        if let Some(parent) = output_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(&output_path, data)
+            .with_context(|| format!("failed to write file `{}`", output_path.display()))?;
+
+        println!("  Extracted: {}", file_path);
+        file_count += 1;
+
+        if let Some(entry_meta) = fs_metadata.get(file_path) {
+            fs_meta::apply(&output_path, entry_meta)?;
+        }
+    }
+
+    // Symlinks and directories carry no content of their own, so they're
+    // only recreated here from the sidecar rather than the content loop
+    // above
+    if preserve {
+        for (entry_path, entry_meta) in &fs_metadata {
+            let output_path = output_dir.join(entry_path);
+            match &entry_meta.kind {
+                EntryKind::Directory => {
+                    fs::create_dir_all(&output_path)?;
+                    fs_meta::apply(&output_path, entry_meta)?;
+                }
+                EntryKind::Symlink { .. } => {
+                    if let Some(parent) = output_path.parent() {
+                        fs::create_dir_all(parent)?;
+                    }
+                    fs_meta::apply(&output_path, entry_meta)?;
+                    println!("  Extracted: {} (symlink)", entry_path);
+                }
+                EntryKind::Regular => {}
+            }
+        }
+    }
+
+    println!("Extraction complete ({} files)", file_count);
+
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,306 @@
+//! Directory-wide catalog over Engram archives
+//!
+//! `search` and `query` operate on one archive at a time and re-scan every
+//! file on each invocation. For a directory holding many `.eng` archives,
+//! building a catalog once — one row per contained file, recording its
+//! owning archive, size, content hash, and database flag — lets later
+//! commands select just the archives/files they actually need instead of
+//! opening every member on every run.
+
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crate::formats::{is_database_path, Archive, EngramArchive};
+
+/// One row of the catalog: a single file inside a single archive.
+#[derive(Debug, Clone)]
+pub struct CatalogEntry {
+    pub archive_path: PathBuf,
+    pub file_path: String,
+    pub size: u64,
+    /// BLAKE3 digest of the file's contents, hex-encoded.
+    pub hash: String,
+    pub is_database: bool,
+}
+
+/// A built catalog: every file row across every scanned archive.
+#[derive(Debug, Clone, Default)]
+pub struct Catalog {
+    pub entries: Vec<CatalogEntry>,
+}
+
+/// Criteria for selecting a subset of catalog rows.
+#[derive(Debug, Clone, Default)]
+pub struct CatalogFilter {
+    pub glob: Option<String>,
+    pub min_size: Option<u64>,
+    pub max_size: Option<u64>,
+    pub hash: Option<String>,
+    pub databases_only: bool,
+}
+
+impl Catalog {
+    /// Build a catalog by opening every `.eng` archive directly inside `dir`
+    /// and hashing each contained file.
+    pub fn build(dir: &Path) -> Result<Self> {
+        let mut entries = Vec::new();
+
+        let read_dir = std::fs::read_dir(dir)
+            .with_context(|| format!("Failed to read directory: {}", dir.display()))?;
+
+        for item in read_dir {
+            let path = item?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("eng") {
+                continue;
+            }
+
+            let mut archive = EngramArchive::open(&path)
+                .with_context(|| format!("Failed to open archive: {}", path.display()))?;
+
+            for file in archive.list_files()? {
+                let data = archive
+                    .read_file(&file.path)
+                    .with_context(|| format!("Failed to read '{}' from {}", file.path, path.display()))?;
+
+                entries.push(CatalogEntry {
+                    archive_path: path.clone(),
+                    is_database: is_database_path(&file.path),
+                    file_path: file.path,
+                    size: file.size,
+                    hash: blake3::hash(&data).to_hex().to_string(),
+                });
+            }
+        }
+
+        Ok(Self { entries })
+    }
+
+    /// Select rows matching `filter`.
+    pub fn select(&self, filter: &CatalogFilter) -> Vec<&CatalogEntry> {
+        self.entries
+            .iter()
+            .filter(|e| {
+                if filter.databases_only && !e.is_database {
+                    return false;
+                }
+                if filter.min_size.is_some_and(|min| e.size < min) {
+                    return false;
+                }
+                if filter.max_size.is_some_and(|max| e.size > max) {
+                    return false;
+                }
+                if let Some(hash) = &filter.hash {
+                    if hash != &e.hash {
+                        return false;
+                    }
+                }
+                if let Some(pattern) = &filter.glob {
+                    match glob::Pattern::new(pattern) {
+                        Ok(p) => {
+                            if !p.matches(&e.file_path) {
+                                return false;
+                            }
+                        }
+                        Err(_) => return false,
+                    }
+                }
+                true
+            })
+            .collect()
+    }
+
+    /// Archive paths touched by `entries`, deduplicated and in first-seen
+    /// order, so callers can open each matching archive exactly once.
+    pub fn matching_archives(entries: &[&CatalogEntry]) -> Vec<PathBuf> {
+        let mut seen = HashSet::new();
+        let mut archives = Vec::new();
+
+        for entry in entries {
+            if seen.insert(entry.archive_path.clone()) {
+                archives.push(entry.archive_path.clone());
+            }
+        }
+
+        archives
+    }
+
+    /// Persist the catalog as CSV, one row per file.
+    ///
+    /// `archive_path`/`file_path` are quoted per [`csv_quote`] since either
+    /// can legally contain a comma; the other columns are numbers/a fixed
+    /// hex digest/a bool and never need it.
+    pub fn write_csv(&self, path: &Path) -> Result<()> {
+        let mut out = String::from("archive_path,file_path,size,hash,is_database\n");
+        for e in &self.entries {
+            out.push_str(&format!(
+                "{},{},{},{},{}\n",
+                csv_quote(&e.archive_path.display().to_string()),
+                csv_quote(&e.file_path),
+                e.size,
+                e.hash,
+                e.is_database
+            ));
+        }
+
+        std::fs::write(path, out)
+            .with_context(|| format!("Failed to write catalog CSV: {}", path.display()))
+    }
+
+    /// Load a catalog previously persisted with [`write_csv`](Catalog::write_csv).
+    pub fn load_csv(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read catalog CSV: {}", path.display()))?;
+
+        let mut entries = Vec::new();
+        for line in content.lines().skip(1) {
+            if line.is_empty() {
+                continue;
+            }
+
+            let fields = parse_csv_line(line);
+            let [archive_path, file_path, size, hash, is_database] = &fields[..] else {
+                continue;
+            };
+
+            entries.push(CatalogEntry {
+                archive_path: PathBuf::from(archive_path),
+                file_path: file_path.to_string(),
+                size: size.parse().unwrap_or(0),
+                hash: hash.to_string(),
+                is_database: is_database == "true",
+            });
+        }
+
+        Ok(Self { entries })
+    }
+
+    /// Persist the catalog to a SQLite sidecar database as a `files` table,
+    /// so it can be queried directly (e.g. by Cartridge's embedded SQLite
+    /// tooling) rather than only filtered in-process.
+    pub fn write_sqlite(&self, path: &Path) -> Result<()> {
+        if path.exists() {
+            std::fs::remove_file(path)
+                .with_context(|| format!("Failed to remove existing catalog: {}", path.display()))?;
+        }
+
+        let conn = rusqlite::Connection::open(path)
+            .with_context(|| format!("Failed to create catalog database: {}", path.display()))?;
+
+        conn.execute_batch(
+            "CREATE TABLE files (
+                archive_path TEXT NOT NULL,
+                file_path TEXT NOT NULL,
+                size INTEGER NOT NULL,
+                hash TEXT NOT NULL,
+                is_database INTEGER NOT NULL
+            );",
+        )
+        .context("Failed to create catalog schema")?;
+
+        for e in &self.entries {
+            conn.execute(
+                "INSERT INTO files (archive_path, file_path, size, hash, is_database) VALUES (?1, ?2, ?3, ?4, ?5)",
+                rusqlite::params![
+                    e.archive_path.to_string_lossy(),
+                    e.file_path,
+                    e.size,
+                    e.hash,
+                    e.is_database as i64,
+                ],
+            )
+            .with_context(|| format!("Failed to insert catalog row for '{}'", e.file_path))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Quote `field` for [`Catalog::write_csv`] if it contains a comma, quote, or
+/// newline, doubling any embedded quotes - minimal RFC 4180 escaping so a
+/// comma in an `archive_path`/`file_path` can't shift every later column.
+fn csv_quote(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Split one [`Catalog::write_csv`] line back into its fields, honoring
+/// double-quoted fields produced by [`csv_quote`]. Doesn't handle a raw
+/// newline embedded in a quoted field, since rows are read one physical
+/// line at a time; that's not a case `write_csv` needs to produce correctly
+/// for, since every other field is numeric/fixed-format.
+fn parse_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut chars = line.chars().peekable();
+    let mut in_quotes = false;
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            if c == '"' {
+                if chars.peek() == Some(&'"') {
+                    field.push('"');
+                    chars.next();
+                } else {
+                    in_quotes = false;
+                }
+            } else {
+                field.push(c);
+            }
+        } else if c == '"' && field.is_empty() {
+            in_quotes = true;
+        } else if c == ',' {
+            fields.push(std::mem::take(&mut field));
+        } else {
+            field.push(c);
+        }
+    }
+    fields.push(field);
+
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn comma_in_path_round_trips_through_csv() {
+        let catalog = Catalog {
+            entries: vec![CatalogEntry {
+                archive_path: PathBuf::from("archives/a, b.eng"),
+                file_path: "notes, final.txt".to_string(),
+                size: 1234,
+                hash: "deadbeef".to_string(),
+                is_database: false,
+            }],
+        };
+
+        let dir = std::env::temp_dir().join(format!("strategos-catalog-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("catalog.csv");
+
+        catalog.write_csv(&path).unwrap();
+        let loaded = Catalog::load_csv(&path).unwrap();
+
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(loaded.entries.len(), 1);
+        assert_eq!(loaded.entries[0].archive_path, catalog.entries[0].archive_path);
+        assert_eq!(loaded.entries[0].file_path, catalog.entries[0].file_path);
+        assert_eq!(loaded.entries[0].size, catalog.entries[0].size);
+        assert_eq!(loaded.entries[0].hash, catalog.entries[0].hash);
+        assert_eq!(loaded.entries[0].is_database, catalog.entries[0].is_database);
+    }
+
+    #[test]
+    fn quote_in_path_round_trips_through_csv() {
+        let field = r#"weird "quoted" name.txt"#;
+        let quoted = csv_quote(field);
+        let parsed = parse_csv_line(&quoted);
+        assert_eq!(parsed, vec![field.to_string()]);
+    }
+}
@@ -3,8 +3,135 @@
 //! Commands for mutable Cartridge archives (.cart)
 
 use anyhow::{Context, Result};
+use base64::Engine;
 use cartridge_rs::Cartridge;
-use std::path::Path;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Sidecar metadata for one snapshot, stored as `{id}.json` in the snapshot
+/// directory alongside whatever cartridge-rs itself persists for a full
+/// snapshot's restorable content.
+///
+/// `kind`/`base_id` are new fields layered on top of the full-snapshot
+/// metadata cartridge-rs already writes; older snapshot directories that
+/// predate incremental support simply parse as `kind: "full"`, `base_id:
+/// None` via `#[serde(default)]`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SnapshotMetadata {
+    id: u64,
+    name: String,
+    description: String,
+    timestamp: serde_json::Value,
+    #[serde(default = "default_kind")]
+    kind: String,
+    #[serde(default)]
+    base_id: Option<u64>,
+}
+
+fn default_kind() -> String {
+    "full".to_string()
+}
+
+/// The delta recorded by an incremental snapshot: files to write (or
+/// overwrite) and files to delete, relative to its `base_id`.
+#[derive(Debug, Serialize, Deserialize)]
+struct SnapshotDelta {
+    /// path -> base64-encoded file content
+    upserts: BTreeMap<String, String>,
+    deletes: Vec<String>,
+}
+
+fn meta_path(snapshot_dir: &Path, id: u64) -> PathBuf {
+    snapshot_dir.join(format!("{}.json", id))
+}
+
+fn digest_path(snapshot_dir: &Path, id: u64) -> PathBuf {
+    snapshot_dir.join(format!("{}.digest.json", id))
+}
+
+fn delta_path(snapshot_dir: &Path, id: u64) -> PathBuf {
+    snapshot_dir.join(format!("{}.delta.json", id))
+}
+
+fn load_metadata(snapshot_dir: &Path, id: u64) -> Result<SnapshotMetadata> {
+    let path = meta_path(snapshot_dir, id);
+    let raw = std::fs::read_to_string(&path)
+        .with_context(|| format!("Missing snapshot metadata: {}", path.display()))?;
+    serde_json::from_str(&raw)
+        .with_context(|| format!("Failed to parse snapshot metadata: {}", path.display()))
+}
+
+fn load_digests(snapshot_dir: &Path, id: u64) -> Result<BTreeMap<String, String>> {
+    let path = digest_path(snapshot_dir, id);
+    let raw = std::fs::read_to_string(&path).with_context(|| {
+        format!(
+            "Missing digest manifest for snapshot {} ({}): cannot diff against it",
+            id,
+            path.display()
+        )
+    })?;
+    serde_json::from_str(&raw)
+        .with_context(|| format!("Failed to parse digest manifest: {}", path.display()))
+}
+
+/// Read every file currently in `cart` and hash its content, producing the
+/// full-state digest manifest a snapshot (full or incremental) is diffed
+/// against going forward.
+fn compute_digests(cart: &Cartridge) -> Result<BTreeMap<String, String>> {
+    let mut digests = BTreeMap::new();
+    for path in cart.list("")? {
+        let data = cart
+            .read(&path)
+            .with_context(|| format!("Failed to read '{}' while computing snapshot digest", path))?;
+        digests.insert(path, blake3::hash(&data).to_hex().to_string());
+    }
+    Ok(digests)
+}
+
+/// Next unused snapshot id in `snapshot_dir`, for minting ids for
+/// incremental snapshots (which cartridge-rs has no API to assign, since it
+/// only knows how to create full snapshots).
+/// True for the `{id}.json` metadata file of a snapshot, as opposed to its
+/// `{id}.digest.json`/`{id}.delta.json` sidecars
+fn is_snapshot_meta_file(path: &Path) -> bool {
+    path.extension().and_then(|s| s.to_str()) == Some("json")
+        && path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .map(|s| !s.ends_with(".digest") && !s.ends_with(".delta"))
+            .unwrap_or(false)
+}
+
+/// Every snapshot's metadata in `snapshot_dir`, in no particular order
+fn load_all_metadata(snapshot_dir: &Path) -> Result<Vec<SnapshotMetadata>> {
+    let mut metas = Vec::new();
+    if !snapshot_dir.exists() {
+        return Ok(metas);
+    }
+    for entry in std::fs::read_dir(snapshot_dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if !is_snapshot_meta_file(&path) {
+            continue;
+        }
+        if let Ok(raw) = std::fs::read_to_string(&path) {
+            if let Ok(meta) = serde_json::from_str::<SnapshotMetadata>(&raw) {
+                metas.push(meta);
+            }
+        }
+    }
+    Ok(metas)
+}
+
+fn next_snapshot_id(snapshot_dir: &Path) -> Result<u64> {
+    Ok(load_all_metadata(snapshot_dir)?
+        .iter()
+        .map(|m| m.id)
+        .max()
+        .unwrap_or(0)
+        + 1)
+}
 
 /// Create a new Cartridge archive
 pub fn create(slug: &str, title: &str, output: Option<&Path>) -> Result<()> {
@@ -51,23 +178,111 @@ pub fn delete(archive_path: &Path, file_path: &str) -> Result<()> {
 }
 
 /// Create a snapshot of a Cartridge archive
+///
+/// With `base` set, records only the files added, modified, or deleted
+/// relative to that base snapshot's resulting state, instead of the full
+/// archive cartridge-rs would otherwise capture — much smaller for large
+/// archives that barely change between captures.
 pub fn snapshot(
     archive_path: &Path,
     name: String,
     description: String,
     snapshot_dir: &Path,
+    base: Option<u64>,
+    keep: Option<usize>,
 ) -> Result<()> {
     let cart = Cartridge::open(archive_path)
         .with_context(|| format!("Failed to open Cartridge: {}", archive_path.display()))?;
 
-    let snapshot_id = cart.create_snapshot(name.clone(), description, snapshot_dir)?;
+    std::fs::create_dir_all(snapshot_dir)
+        .with_context(|| format!("Failed to create snapshot directory: {}", snapshot_dir.display()))?;
+
+    let current_digests = compute_digests(&cart)?;
+
+    let snapshot_id = match base {
+        None => {
+            let id = cart.create_snapshot(name.clone(), description.clone(), snapshot_dir)?;
+
+            // cartridge-rs already wrote this snapshot's metadata file; layer
+            // our kind/base_id fields on top of it rather than replacing it.
+            let path = meta_path(snapshot_dir, id);
+            let raw = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read snapshot metadata: {}", path.display()))?;
+            let mut value: serde_json::Value = serde_json::from_str(&raw)?;
+            if let Some(obj) = value.as_object_mut() {
+                obj.insert("kind".to_string(), serde_json::json!("full"));
+                obj.insert("base_id".to_string(), serde_json::Value::Null);
+            }
+            std::fs::write(&path, serde_json::to_string_pretty(&value)?)?;
+
+            id
+        }
+        Some(base_id) => {
+            let base_digests = load_digests(snapshot_dir, base_id)?;
+
+            let mut upserts = BTreeMap::new();
+            let mut deletes = Vec::new();
+
+            for (path, digest) in &current_digests {
+                if base_digests.get(path) != Some(digest) {
+                    let data = cart
+                        .read(path)
+                        .with_context(|| format!("Failed to read '{}' for incremental snapshot", path))?;
+                    upserts.insert(path.clone(), base64::engine::general_purpose::STANDARD.encode(&data));
+                }
+            }
+            for path in base_digests.keys() {
+                if !current_digests.contains_key(path) {
+                    deletes.push(path.clone());
+                }
+            }
+
+            let id = next_snapshot_id(snapshot_dir)?;
+            let delta = SnapshotDelta { upserts, deletes };
+            std::fs::write(delta_path(snapshot_dir, id), serde_json::to_string_pretty(&delta)?)
+                .with_context(|| format!("Failed to write delta for snapshot {}", id))?;
+
+            let meta = SnapshotMetadata {
+                id,
+                name: name.clone(),
+                description: description.clone(),
+                timestamp: serde_json::json!(current_unix_timestamp()),
+                kind: "incremental".to_string(),
+                base_id: Some(base_id),
+            };
+            std::fs::write(meta_path(snapshot_dir, id), serde_json::to_string_pretty(&meta)?)
+                .with_context(|| format!("Failed to write metadata for snapshot {}", id))?;
+
+            id
+        }
+    };
+
+    std::fs::write(
+        digest_path(snapshot_dir, snapshot_id),
+        serde_json::to_string_pretty(&current_digests)?,
+    )
+    .with_context(|| format!("Failed to write digest manifest for snapshot {}", snapshot_id))?;
 
     println!("✅ Created snapshot: {} (ID: {})", name, snapshot_id);
     println!("   Stored in: {}", snapshot_dir.display());
+    if let Some(base_id) = base {
+        println!("   Incremental from snapshot {}", base_id);
+    }
+
+    if let Some(keep) = keep {
+        prune(snapshot_dir, keep, false)?;
+    }
 
     Ok(())
 }
 
+fn current_unix_timestamp() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
 /// Freeze a Cartridge archive to Engram format (immutable)
 pub fn freeze(cartridge_path: &Path, engram_output: &Path) -> Result<()> {
     // This would require engram_integration from cartridge-rs
@@ -82,6 +297,10 @@ pub fn freeze(cartridge_path: &Path, engram_output: &Path) -> Result<()> {
 }
 
 /// List snapshots of a Cartridge archive
+///
+/// Renders the incremental chain visually: each incremental snapshot shows
+/// the base it derives from, so the sequence needed to restore it is clear
+/// at a glance.
 pub fn list_snapshots(snapshot_dir: &Path) -> Result<()> {
     if !snapshot_dir.exists() {
         println!("No snapshots found in: {}", snapshot_dir.display());
@@ -91,34 +310,174 @@ pub fn list_snapshots(snapshot_dir: &Path) -> Result<()> {
     println!("📸 Snapshots in: {}", snapshot_dir.display());
     println!("{}", "─".repeat(60));
 
-    // List snapshot metadata files
-    for entry in std::fs::read_dir(snapshot_dir)? {
-        let entry = entry?;
-        let path = entry.path();
+    let mut metas = load_all_metadata(snapshot_dir)?;
+    metas.sort_by_key(|m| m.id);
+
+    for meta in metas {
+        println!("Snapshot ID: {}", meta.id);
+        println!("Name:        {}", meta.name);
+        println!("Description: {}", meta.description);
+        println!("Created:     {}", meta.timestamp);
+        match meta.base_id {
+            Some(base_id) => println!("Kind:        incremental (base: {})", base_id),
+            None => println!("Kind:        {}", meta.kind),
+        }
+        println!();
+    }
+
+    Ok(())
+}
+
+/// Enforce a retention policy over `snapshot_dir`: keep the `keep` most
+/// recent snapshots by timestamp, deleting older ones and their backing
+/// data. A full snapshot is never deleted while any retained incremental's
+/// `base_id` chain still depends on it, so the chain needed to restore a
+/// kept snapshot always stays intact.
+pub fn prune(snapshot_dir: &Path, keep: usize, dry_run: bool) -> Result<()> {
+    let mut metas = load_all_metadata(snapshot_dir)?;
+    metas.sort_by_key(|m| std::cmp::Reverse(timestamp_sort_key(&m.timestamp)));
+
+    let by_id: BTreeMap<u64, &SnapshotMetadata> = metas.iter().map(|m| (m.id, m)).collect();
+
+    let mut retained: std::collections::BTreeSet<u64> =
+        metas.iter().take(keep).map(|m| m.id).collect();
+
+    // Protect every ancestor a retained incremental's base_id chain depends on.
+    let mut frontier: Vec<u64> = retained.iter().copied().collect();
+    while let Some(id) = frontier.pop() {
+        if let Some(base_id) = by_id.get(&id).and_then(|m| m.base_id) {
+            if retained.insert(base_id) {
+                frontier.push(base_id);
+            }
+        }
+    }
+
+    let to_remove: Vec<&SnapshotMetadata> = metas.iter().filter(|m| !retained.contains(&m.id)).collect();
 
-        if path.extension().and_then(|s| s.to_str()) == Some("json") {
-            let metadata = std::fs::read_to_string(&path)?;
-            let meta: serde_json::Value = serde_json::from_str(&metadata)?;
+    if to_remove.is_empty() {
+        println!("Nothing to prune in: {}", snapshot_dir.display());
+        return Ok(());
+    }
 
-            println!("Snapshot ID: {}", meta["id"]);
-            println!("Name:        {}", meta["name"]);
-            println!("Description: {}", meta["description"]);
-            println!("Created:     {}", meta["timestamp"]);
-            println!();
+    for meta in &to_remove {
+        if dry_run {
+            println!("Would remove snapshot {} ({})", meta.id, meta.name);
+        } else {
+            remove_snapshot_files(snapshot_dir, meta.id)?;
+            println!("Removed snapshot {} ({})", meta.id, meta.name);
         }
     }
 
+    if dry_run {
+        println!("Dry run: {} snapshot(s) would be removed, {} retained", to_remove.len(), retained.len());
+    } else {
+        println!("Pruned {} snapshot(s), {} retained", to_remove.len(), retained.len());
+    }
+
+    Ok(())
+}
+
+/// Best-effort numeric ordering key for a snapshot's `timestamp`, which may
+/// be a number (our own snapshots) or any other JSON value cartridge-rs
+/// happened to write for a legacy full snapshot
+fn timestamp_sort_key(value: &serde_json::Value) -> i64 {
+    value.as_i64().or_else(|| value.as_u64().map(|v| v as i64)).unwrap_or(0)
+}
+
+/// Delete every file belonging to snapshot `id`: our own metadata/digest/
+/// delta sidecars, plus whatever other artifact cartridge-rs itself wrote
+/// for a full snapshot's restorable content (its filename scheme isn't
+/// public, so we sweep for any file in the directory named `{id}.*`).
+fn remove_snapshot_files(snapshot_dir: &Path, id: u64) -> Result<()> {
+    let prefix = format!("{}.", id);
+    for entry in std::fs::read_dir(snapshot_dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let matches = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .map(|n| n == format!("{}.json", id) || n.starts_with(&prefix))
+            .unwrap_or(false);
+        if matches {
+            std::fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove snapshot artifact: {}", path.display()))?;
+        }
+    }
     Ok(())
 }
 
 /// Restore a Cartridge from a snapshot
+///
+/// If `snapshot_id` names an incremental snapshot, walks its `base_id` chain
+/// back to the nearest full snapshot, restores that, then replays each
+/// intervening delta in order (upserts, then deletions) to reconstruct the
+/// requested state.
 pub fn restore(archive_path: &Path, snapshot_id: u64, snapshot_dir: &Path) -> Result<()> {
+    let meta = load_metadata(snapshot_dir, snapshot_id)?;
+
+    if meta.kind == "full" || meta.base_id.is_none() {
+        let mut cart = Cartridge::open(archive_path)
+            .with_context(|| format!("Failed to open Cartridge: {}", archive_path.display()))?;
+        cart.restore_snapshot(snapshot_id, snapshot_dir)?;
+        println!("✅ Restored snapshot {} to: {}", snapshot_id, archive_path.display());
+        return Ok(());
+    }
+
+    // Walk back to the nearest full snapshot, collecting the chain of
+    // incrementals to replay (in base-to-target order) along the way.
+    let mut chain = vec![snapshot_id];
+    let mut cursor = meta;
+    let full_id = loop {
+        match cursor.base_id {
+            None => break cursor.id,
+            Some(base_id) => {
+                let base_meta = load_metadata(snapshot_dir, base_id).with_context(|| {
+                    format!(
+                        "Broken snapshot chain: base {} of snapshot {} is missing from {}",
+                        base_id,
+                        cursor.id,
+                        snapshot_dir.display()
+                    )
+                })?;
+                chain.push(base_id);
+                if base_meta.kind == "full" {
+                    break base_id;
+                }
+                cursor = base_meta;
+            }
+        }
+    };
+    chain.reverse(); // now ordered full -> ... -> target
+
     let mut cart = Cartridge::open(archive_path)
         .with_context(|| format!("Failed to open Cartridge: {}", archive_path.display()))?;
+    cart.restore_snapshot(full_id, snapshot_dir)?;
+
+    for id in chain.into_iter().filter(|&id| id != full_id) {
+        let path = delta_path(snapshot_dir, id);
+        let raw = std::fs::read_to_string(&path)
+            .with_context(|| format!("Missing delta for snapshot {}: {}", id, path.display()))?;
+        let delta: SnapshotDelta = serde_json::from_str(&raw)
+            .with_context(|| format!("Failed to parse delta for snapshot {}", id))?;
 
-    cart.restore_snapshot(snapshot_id, snapshot_dir)?;
+        for (path, encoded) in &delta.upserts {
+            let data = base64::engine::general_purpose::STANDARD
+                .decode(encoded)
+                .with_context(|| format!("Corrupt delta entry for '{}' in snapshot {}", path, id))?;
+            cart.write(path, &data)?;
+        }
+        for path in &delta.deletes {
+            cart.delete(path)?;
+        }
+    }
+    cart.flush()?;
 
-    println!("✅ Restored snapshot {} to: {}", snapshot_id, archive_path.display());
+    println!(
+        "✅ Restored snapshot {} to: {} (replayed from full snapshot {})",
+        snapshot_id,
+        archive_path.display(),
+        full_id
+    );
 
     Ok(())
 }
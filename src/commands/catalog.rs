@@ -0,0 +1,119 @@
+//! Catalog command - build and select across a directory-wide manifest
+
+use anyhow::{Context, Result};
+use std::path::Path;
+
+use crate::catalog::{Catalog, CatalogFilter};
+use crate::formats::traits::OutputFormat;
+use crate::formats::{Archive, EngramArchive, QueryableArchive};
+
+/// Build a catalog over every `.eng` archive in `dir` and persist it to
+/// `output` (CSV, or SQLite if the extension is `.db`/`.sqlite`/`.sqlite3`).
+pub fn build(dir: &Path, output: &Path) -> Result<()> {
+    let catalog =
+        Catalog::build(dir).with_context(|| format!("Failed to build catalog over {}", dir.display()))?;
+
+    match output.extension().and_then(|e| e.to_str()) {
+        Some("db") | Some("sqlite") | Some("sqlite3") => catalog.write_sqlite(output)?,
+        _ => catalog.write_csv(output)?,
+    }
+
+    let archive_count =
+        Catalog::matching_archives(&catalog.entries.iter().collect::<Vec<_>>()).len();
+    println!(
+        "Cataloged {} file(s) across {} archive(s) -> {}",
+        catalog.entries.len(),
+        archive_count,
+        output.display()
+    );
+
+    Ok(())
+}
+
+fn load(catalog_path: &Path) -> Result<Catalog> {
+    Catalog::load_csv(catalog_path)
+        .with_context(|| format!("Failed to load catalog: {}", catalog_path.display()))
+}
+
+/// Search only the archives whose catalog rows match `glob`, instead of
+/// every archive in a collection.
+pub fn search(
+    catalog_path: &Path,
+    glob: Option<&str>,
+    pattern: &str,
+    options: &crate::search::SearchOptions,
+    json: bool,
+) -> Result<()> {
+    let catalog = load(catalog_path)?;
+    let filter = CatalogFilter {
+        glob: glob.map(String::from),
+        ..Default::default()
+    };
+
+    let rows = catalog.select(&filter);
+    let archives = Catalog::matching_archives(&rows);
+
+    if archives.is_empty() {
+        println!("No catalog entries matched");
+        return Ok(());
+    }
+
+    let mut results = Vec::new();
+    for archive_path in archives {
+        for mut result in crate::search::parallel_search::<EngramArchive>(&archive_path, pattern, options)? {
+            result.file_path = format!("{}:{}", archive_path.display(), result.file_path);
+            results.push(result);
+        }
+    }
+
+    super::shared::print_results(&results, json);
+    Ok(())
+}
+
+/// Query only the archives whose catalog rows match `glob`/database flag,
+/// instead of opening every archive in a collection up front.
+pub fn query(
+    catalog_path: &Path,
+    glob: Option<&str>,
+    database: Option<&str>,
+    sql: Option<&str>,
+    format: &str,
+) -> Result<()> {
+    let catalog = load(catalog_path)?;
+    let filter = CatalogFilter {
+        glob: glob.map(String::from),
+        databases_only: true,
+        ..Default::default()
+    };
+
+    let rows = catalog.select(&filter);
+    let archives = Catalog::matching_archives(&rows);
+
+    if archives.is_empty() {
+        println!("No catalog entries matched");
+        return Ok(());
+    }
+
+    let output_format = match format {
+        "json" => OutputFormat::Json,
+        "csv" => OutputFormat::Csv,
+        _ => OutputFormat::Table,
+    };
+
+    let (Some(database), Some(sql)) = (database, sql) else {
+        for archive_path in archives {
+            println!("{}", archive_path.display());
+        }
+        return Ok(());
+    };
+
+    for archive_path in archives {
+        let mut archive = EngramArchive::open(&archive_path)?;
+        match archive.query(database, sql, output_format) {
+            Ok(result) => println!("== {} ==\n{}", archive_path.display(), result),
+            Err(_) => continue, // archive doesn't contain this database path; skip it
+        }
+    }
+
+    Ok(())
+}
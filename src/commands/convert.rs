@@ -0,0 +1,104 @@
+//! Convert command - Migrate archive contents between formats
+//!
+//! Reads any source archive through the `Archive` trait and re-writes its
+//! contents into a target archive through `MutableArchive::write_file`,
+//! giving users a migration path into/out of the native formats (e.g.
+//! `foo.tar.gz` -> `.cart`).
+
+use anyhow::{Context, Result};
+use std::path::Path;
+
+use crate::formats::{
+    detect_format, detect_format_from_extension, Archive, ArchiveFormat, CartridgeArchive,
+    DataSpoolArchive, MutableArchive, TarArchive, ZipArchive,
+};
+
+pub fn convert(source: &Path, destination: &Path) -> Result<()> {
+    let source_format = detect_format(source)?;
+    // Extension-only: `detect_format` opens the file to sniff magic bytes,
+    // but the destination usually doesn't exist yet - that's the whole
+    // point of `convert`.
+    let dest_format = detect_format_from_extension(destination);
+
+    println!(
+        "Converting {} ({}) -> {} ({})",
+        source.display(),
+        source_format.name(),
+        destination.display(),
+        dest_format.name()
+    );
+
+    let mut reader: Box<dyn Archive> = match source_format {
+        ArchiveFormat::Tar => Box::new(TarArchive::open(source)?),
+        ArchiveFormat::Zip => Box::new(ZipArchive::open(source)?),
+        ArchiveFormat::Cartridge => Box::new(CartridgeArchive::open(source)?),
+        ArchiveFormat::DataSpool | ArchiveFormat::DataSpoolSplit => {
+            Box::new(DataSpoolArchive::open(source)?)
+        }
+        ArchiveFormat::Engram | ArchiveFormat::DataCard => {
+            anyhow::bail!(
+                "{} archives are read-only sources for convert; open one of the mutable \
+                 formats (Cartridge, DataSpool, Tar, Zip) as the source instead",
+                source_format.name()
+            )
+        }
+        ArchiveFormat::Unknown => anyhow::bail!("Unknown archive format: {}", source.display()),
+    };
+
+    let files = reader.list_files()?;
+    let count = files.len();
+
+    match dest_format {
+        ArchiveFormat::Cartridge => {
+            let mut writer = if destination.exists() {
+                CartridgeArchive::open(destination).with_context(|| {
+                    format!("Failed to open Cartridge: {}", destination.display())
+                })?
+            } else {
+                let slug = destination
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or("archive");
+                CartridgeArchive::create(destination, slug, slug).with_context(|| {
+                    format!("Failed to create Cartridge: {}", destination.display())
+                })?
+            };
+            copy_all(reader.as_mut(), &mut writer, &files)?;
+        }
+        ArchiveFormat::Zip => {
+            let mut writer = ZipArchive::open(destination)?;
+            copy_all(reader.as_mut(), &mut writer, &files)?;
+        }
+        ArchiveFormat::Engram
+        | ArchiveFormat::DataSpool
+        | ArchiveFormat::DataSpoolSplit
+        | ArchiveFormat::DataCard => {
+            anyhow::bail!(
+                "{} is not yet a supported convert destination (no writable archive type)",
+                dest_format.name()
+            )
+        }
+        ArchiveFormat::Tar => anyhow::bail!("Tar is not a supported convert destination"),
+        ArchiveFormat::Unknown => {
+            anyhow::bail!("Unknown destination format: {}", destination.display())
+        }
+    }
+
+    println!("✅ Converted {} files", count);
+    Ok(())
+}
+
+fn copy_all(
+    reader: &mut dyn Archive,
+    writer: &mut dyn MutableArchive,
+    files: &[crate::formats::FileEntry],
+) -> Result<()> {
+    for entry in files {
+        let data = reader.read_file(&entry.path)?;
+        writer
+            .write_file(&entry.path, &data)
+            .with_context(|| format!("Failed to write '{}' to destination archive", entry.path))?;
+        println!("  {}", entry.path);
+    }
+    writer.flush()
+}
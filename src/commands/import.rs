@@ -0,0 +1,260 @@
+//! Import command - Migrate foreign archives into Engram
+//!
+//! Unlike `convert`, which reads any source through the `Archive` trait,
+//! `import` is aimed at formats Strategos never mounts as an `Archive`:
+//! plain and compressed tarballs, zip, and ar. The source format is
+//! sniffed from its leading magic bytes rather than trusted from the file
+//! extension, a decompressor is chained in front of a tar reader for the
+//! compressed-tar cases, and every regular-file entry is streamed straight
+//! into a new Engram archive through `ArchiveWriter`, the same writer
+//! `pack` uses. Entry paths go through the same
+//! [`crate::extract::validate_path_components`] check extraction does, so
+//! a hostile `../../etc/passwd` member in the source archive can't escape
+//! anywhere - there's nowhere for it to escape to, since every entry lands
+//! inside the new archive rather than on disk.
+
+use anyhow::{Context, Result};
+use engram_rs::{ArchiveWriter, CompressionMethod};
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use crate::extract::validate_path_components;
+use crate::utils::compression::parse_compression;
+
+const GZIP_MAGIC: &[u8] = &[0x1F, 0x8B];
+const XZ_MAGIC: &[u8] = &[0xFD, 0x37, 0x7A, 0x58, 0x5A, 0x00];
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xB5, 0x2F, 0xFD];
+const BZIP2_MAGIC: &[u8] = b"BZh";
+const ZIP_MAGIC: &[u8] = b"PK\x03\x04";
+const AR_MAGIC: &[u8] = b"!<arch>\n";
+const TAR_USTAR_MAGIC: &[u8] = b"ustar";
+const TAR_USTAR_OFFSET: u64 = 257;
+
+/// Foreign container format an import source was sniffed as
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SourceKind {
+    Tar,
+    TarGz,
+    TarXz,
+    TarZstd,
+    TarBz2,
+    Zip,
+    Ar,
+}
+
+impl SourceKind {
+    fn name(&self) -> &'static str {
+        match self {
+            SourceKind::Tar => "tar",
+            SourceKind::TarGz => "tar.gz",
+            SourceKind::TarXz => "tar.xz",
+            SourceKind::TarZstd => "tar.zst",
+            SourceKind::TarBz2 => "tar.bz2",
+            SourceKind::Zip => "zip",
+            SourceKind::Ar => "ar",
+        }
+    }
+}
+
+/// Import `source` into a new Engram archive at `output_path` (defaulting
+/// to `source` with every recognized extension stripped and `.eng` added).
+pub fn import(source: &Path, output_path: Option<&Path>, compression_str: &str) -> Result<()> {
+    let kind = sniff(source)?;
+    let compression = parse_compression(compression_str)?;
+
+    let output = match output_path {
+        Some(p) => p.to_path_buf(),
+        None => default_output_path(source),
+    };
+
+    println!("Importing: {} ({})", source.display(), kind.name());
+    println!("Output: {}", output.display());
+
+    let mut writer = ArchiveWriter::create(&output)
+        .with_context(|| format!("Failed to create archive `{}`", output.display()))?;
+
+    let file_count = match kind {
+        SourceKind::Zip => import_zip(source, &mut writer, compression)?,
+        SourceKind::Ar => import_ar(source, &mut writer, compression)?,
+        SourceKind::Tar => {
+            let file = open(source)?;
+            import_tar(Box::new(file), &mut writer, compression)?
+        }
+        SourceKind::TarGz => {
+            let file = open(source)?;
+            import_tar(Box::new(flate2::read::GzDecoder::new(file)), &mut writer, compression)?
+        }
+        SourceKind::TarXz => {
+            let file = open(source)?;
+            import_tar(Box::new(xz2::read::XzDecoder::new(file)), &mut writer, compression)?
+        }
+        SourceKind::TarZstd => {
+            let file = open(source)?;
+            let decoder = zstd::stream::read::Decoder::new(file)
+                .context("Failed to initialize zstd decoder")?;
+            import_tar(Box::new(decoder), &mut writer, compression)?
+        }
+        SourceKind::TarBz2 => {
+            let file = open(source)?;
+            import_tar(Box::new(bzip2::read::BzDecoder::new(file)), &mut writer, compression)?
+        }
+    };
+
+    writer
+        .finalize()
+        .with_context(|| format!("Failed to finalize archive `{}`", output.display()))?;
+
+    println!("Packed {} files", file_count);
+    println!("Archive created successfully: {}", output.display());
+
+    Ok(())
+}
+
+fn open(path: &Path) -> Result<File> {
+    File::open(path).with_context(|| format!("Failed to open file: {}", path.display()))
+}
+
+/// Strip every trailing extension this module recognizes (so
+/// `archive.tar.gz` becomes `archive.eng`, not `archive.tar.eng`) and add
+/// `.eng`.
+fn default_output_path(source: &Path) -> std::path::PathBuf {
+    let mut output = source.to_path_buf();
+    while let Some(ext) = output.extension().and_then(|e| e.to_str()) {
+        if matches!(
+            ext.to_lowercase().as_str(),
+            "tar" | "gz" | "tgz" | "xz" | "zst" | "zstd" | "bz2" | "tbz2" | "zip" | "ar"
+        ) {
+            output.set_extension("");
+        } else {
+            break;
+        }
+    }
+    output.set_extension("eng");
+    output
+}
+
+/// Sniff `source`'s container format from its leading magic bytes,
+/// falling back to the "ustar" tag at byte offset 257 for bare tar (which
+/// has no magic of its own at the start of the file).
+fn sniff(source: &Path) -> Result<SourceKind> {
+    let mut file = open(source)?;
+
+    let mut header = [0u8; 8];
+    let n = file.read(&mut header).with_context(|| {
+        format!("Failed to read header of `{}`", source.display())
+    })?;
+
+    if n >= 2 && header[0..2] == *GZIP_MAGIC {
+        return Ok(SourceKind::TarGz);
+    }
+    if n >= 6 && header[0..6] == *XZ_MAGIC {
+        return Ok(SourceKind::TarXz);
+    }
+    if n >= 4 && header[0..4] == *ZSTD_MAGIC {
+        return Ok(SourceKind::TarZstd);
+    }
+    if n >= 3 && header[0..3] == *BZIP2_MAGIC {
+        return Ok(SourceKind::TarBz2);
+    }
+    if n >= 4 && header[0..4] == *ZIP_MAGIC {
+        return Ok(SourceKind::Zip);
+    }
+    if n >= 8 && header == *AR_MAGIC {
+        return Ok(SourceKind::Ar);
+    }
+
+    use std::io::{Seek, SeekFrom};
+    let mut tag = [0u8; 5];
+    if file
+        .seek(SeekFrom::Start(TAR_USTAR_OFFSET))
+        .ok()
+        .and_then(|_| file.read_exact(&mut tag).ok())
+        .is_some()
+        && tag == *TAR_USTAR_MAGIC
+    {
+        return Ok(SourceKind::Tar);
+    }
+
+    anyhow::bail!(
+        "'{}' is not a recognized tar/gzip/xz/zstd/bzip2/zip/ar archive",
+        source.display()
+    )
+}
+
+/// Walk a (possibly decompressed) tar stream, writing every regular-file
+/// entry straight into `writer`
+fn import_tar(reader: Box<dyn Read>, writer: &mut ArchiveWriter, compression: CompressionMethod) -> Result<usize> {
+    let mut archive = tar::Archive::new(reader);
+    let mut count = 0;
+
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if entry.header().entry_type() != tar::EntryType::Regular {
+            continue;
+        }
+
+        let path = entry.path()?.to_string_lossy().replace('\\', "/");
+        validate_path_components(&path)?;
+
+        let mut data = Vec::new();
+        entry.read_to_end(&mut data)?;
+        writer.add_file_with_compression(&path, &data, compression)?;
+        println!("  Added: {}", path);
+        count += 1;
+    }
+
+    Ok(count)
+}
+
+/// Walk a zip archive, writing every non-directory entry straight into
+/// `writer`
+fn import_zip(source: &Path, writer: &mut ArchiveWriter, compression: CompressionMethod) -> Result<usize> {
+    let file = open(source)?;
+    let mut zip = zip::ZipArchive::new(file)
+        .with_context(|| format!("Failed to read zip archive: {}", source.display()))?;
+    let mut count = 0;
+
+    for i in 0..zip.len() {
+        let mut entry = zip.by_index(i)?;
+        if entry.is_dir() {
+            continue;
+        }
+
+        let path = entry.name().replace('\\', "/");
+        validate_path_components(&path)?;
+
+        let mut data = Vec::new();
+        entry.read_to_end(&mut data)?;
+        writer.add_file_with_compression(&path, &data, compression)?;
+        println!("  Added: {}", path);
+        count += 1;
+    }
+
+    Ok(count)
+}
+
+/// Walk an ar archive, writing every member straight into `writer`
+fn import_ar(source: &Path, writer: &mut ArchiveWriter, compression: CompressionMethod) -> Result<usize> {
+    let file = open(source)?;
+    let mut archive = ar::Archive::new(file);
+    let mut count = 0;
+
+    while let Some(entry) = archive.next_entry() {
+        let mut entry =
+            entry.with_context(|| format!("Failed to read ar member in `{}`", source.display()))?;
+
+        let path = String::from_utf8_lossy(entry.header().identifier())
+            .trim_end()
+            .to_string();
+        validate_path_components(&path)?;
+
+        let mut data = Vec::new();
+        entry.read_to_end(&mut data)?;
+        writer.add_file_with_compression(&path, &data, compression)?;
+        println!("  Added: {}", path);
+        count += 1;
+    }
+
+    Ok(count)
+}
@@ -3,10 +3,15 @@
 // Shared commands (work across all formats)
 pub mod shared;
 
+// Directory-wide catalog over Engram archives
+pub mod catalog;
+
 // Format-specific commands
 pub mod cartridge;
 pub mod dataspool;
 pub mod datacard;
+pub mod convert;
+pub mod import;
 
 // Legacy Engram-specific commands
 pub mod extract;
@@ -0,0 +1,563 @@
+//! Pack command - Create Engram archives
+
+use anyhow::{Context, Result};
+use engram_rs::{ArchiveReader, ArchiveWriter, CompressionMethod};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+use walkdir::WalkDir;
+
+use crate::crypto::keys::KeyPair;
+use crate::dedup::{chunker::ChunkerConfig, ChunkStore};
+use crate::formats::unix_meta::{self, FsEntryMetadata, FS_METADATA_FILE};
+use crate::manifest::builder::TomlManifest;
+use crate::utils::{compression::parse_compression, paths::normalize_path};
+
+/// Pack `source_path` into a new Engram archive.
+///
+/// With `dedup` set, files are split into content-defined chunks (see
+/// [`crate::dedup`]) and stored with only first occurrences kept, rather
+/// than added whole. Each chunk is written once under `chunks/{blake3 hex
+/// digest}`; every original path is instead recorded as an ordered list of
+/// chunk digests in a top-level `_dedup_manifest.json`, which
+/// [`crate::formats::engram::EngramArchive`] consults on every read
+/// (`list`/`info`/`extract`/`search`/`--stdout`) to transparently
+/// reassemble the original file from its chunks.
+///
+/// With `preserve_metadata` set, the directory walk also captures
+/// directories, symlinks, and device/fifo nodes instead of skipping
+/// everything but regular files, and records each entry's mode, ownership,
+/// mtime, and xattrs. Since Engram has no native concept of any of this, it
+/// all goes into a top-level `_fs_metadata.json` sidecar (see
+/// [`crate::formats::unix_meta`]) that [`crate::formats::engram::EngramArchive::extract`]
+/// consults to recreate the tree faithfully.
+///
+/// `password` is resolved by the caller (see [`crate::crypto::password`])
+/// whenever `encrypt`/`encrypt_per_file` is set; archive-level encryption
+/// isn't wired up yet, so it's only captured here in preparation for that.
+///
+/// With `append` set, `output` must already exist: its entries are read
+/// back through [`ArchiveReader`] and carried forward into a freshly
+/// rebuilt archive alongside the new ones, since `ArchiveWriter` has no
+/// in-place seek-and-append of its own. The rebuild happens in a `.tmp`
+/// file next to `output` and is only renamed into place once it succeeds,
+/// so a failed append leaves the original archive untouched. A new file
+/// that collides with an existing archive path is rejected unless `force`
+/// is set, and `append` refuses to run if it would change the compression
+/// codec of an existing entry.
+///
+/// With `list` set, the directory is walked and the entries that would be
+/// archived are printed (path, size, and a final human-readable total)
+/// without writing anything at all - every other option is ignored. With
+/// `verify` set, the archive is reopened through `ArchiveReader` after
+/// packing and every file just added is byte-compared against its source
+/// on disk, so a corrupted write is caught immediately instead of surfacing
+/// later in `extract`.
+pub fn pack(
+    source_path: &Path,
+    output_path: Option<&Path>,
+    compression_str: &str,
+    manifest_path: Option<&Path>,
+    sign_key_path: Option<&Path>,
+    encrypt: bool,
+    encrypt_per_file: bool,
+    password: Option<String>,
+    dedup: bool,
+    preserve_metadata: bool,
+    append: bool,
+    force: bool,
+    list: bool,
+    verify: bool,
+) -> Result<()> {
+    if list {
+        return list_entries(source_path);
+    }
+
+    // Determine output path
+    let output = match output_path {
+        Some(p) => p.to_path_buf(),
+        None => {
+            let mut default_output = source_path.to_path_buf();
+            default_output.set_extension("eng");
+            default_output
+        }
+    };
+
+    if append && !output.exists() {
+        anyhow::bail!(
+            "--append requires an existing archive at `{}`",
+            output.display()
+        );
+    }
+
+    println!("Packing: {}", source_path.display());
+    println!("Output: {}", output.display());
+
+    // Parse compression method
+    let compression = parse_compression(compression_str)?;
+
+    // With --append, read every entry already in `output` before creating
+    // the new archive, so they can be carried forward below
+    let existing_entries = if append {
+        read_existing_entries(&output, compression)?
+    } else {
+        Vec::new()
+    };
+
+    // Write into a `.tmp` file when appending so a failure can't corrupt
+    // the archive being extended; otherwise write `output` directly
+    let write_target = if append {
+        output.with_extension("eng.tmp")
+    } else {
+        output.clone()
+    };
+
+    // Create archive writer
+    let mut writer = ArchiveWriter::create(&write_target)
+        .with_context(|| format!("Failed to create archive `{}`", write_target.display()))?;
+
+    // Handle encryption
+    if encrypt && encrypt_per_file {
+        anyhow::bail!("Cannot use both --encrypt and --encrypt-per-file");
+    }
+
+    // TODO: Implement encryption support
+    if password.is_some() {
+        println!("Warning: Encryption not yet implemented in this version (password captured but unused)");
+    }
+
+    // Add manifest if provided
+    if let Some(manifest_file) = manifest_path {
+        let toml_manifest = TomlManifest::load(manifest_file)?;
+        let engram_manifest = toml_manifest.to_engram_manifest();
+
+        // Add manifest.json to archive
+        let manifest_json = serde_json::to_vec_pretty(&engram_manifest)?;
+        writer.add_file_with_compression(
+            "manifest.json",
+            &manifest_json,
+            CompressionMethod::None,
+        )?;
+        println!("  Added: manifest.json");
+    }
+
+    // Collect (archive_path, disk_path) pairs for every regular file to add,
+    // plus (when `preserve_metadata` is set) the mode/ownership/node-type
+    // metadata for every entry, regular or not
+    let metadata = fs::metadata(source_path)
+        .with_context(|| format!("Failed to read metadata for `{}`", source_path.display()))?;
+
+    let mut fs_metadata: BTreeMap<String, FsEntryMetadata> = existing_entries
+        .iter()
+        .find(|e| e.path == FS_METADATA_FILE)
+        .and_then(|e| serde_json::from_slice(&e.data).ok())
+        .unwrap_or_default();
+
+    let files: Vec<(String, std::path::PathBuf)> = if metadata.is_file() {
+        let file_name = source_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .context("Invalid file name")?
+            .to_string();
+
+        if preserve_metadata {
+            let entry_meta = unix_meta::capture(source_path, &metadata)?;
+            fs_metadata.insert(file_name.clone(), entry_meta);
+        }
+
+        vec![(file_name, source_path.to_path_buf())]
+    } else if metadata.is_dir() {
+        let mut files = Vec::new();
+        for entry in WalkDir::new(source_path)
+            .follow_links(false)
+            .sort_by_file_name()
+        {
+            let entry = entry.with_context(|| {
+                format!(
+                    "Failed to read directory entry in `{}`",
+                    source_path.display()
+                )
+            })?;
+
+            // The root directory itself has no archive path of its own
+            if entry.depth() == 0 {
+                continue;
+            }
+
+            let relative_path = entry
+                .path()
+                .strip_prefix(source_path)
+                .with_context(|| {
+                    format!(
+                        "Failed to get relative path for `{}`",
+                        entry.path().display()
+                    )
+                })?
+                .to_str()
+                .context("Invalid file path")?;
+            let archive_path = normalize_path(relative_path);
+
+            if !preserve_metadata {
+                if entry.file_type().is_file() {
+                    files.push((archive_path, entry.path().to_path_buf()));
+                }
+                continue;
+            }
+
+            let symlink_meta = std::fs::symlink_metadata(entry.path()).with_context(|| {
+                format!("Failed to read metadata for `{}`", entry.path().display())
+            })?;
+            let entry_meta = unix_meta::capture(entry.path(), &symlink_meta)?;
+
+            // Only regular files carry content in the archive; directories,
+            // symlinks, and device/fifo nodes are fully described by their
+            // `_fs_metadata.json` entry
+            if symlink_meta.is_file() {
+                files.push((archive_path.clone(), entry.path().to_path_buf()));
+            }
+            fs_metadata.insert(archive_path, entry_meta);
+        }
+        files
+    } else {
+        anyhow::bail!(
+            "Path is neither a file nor a directory: {}",
+            source_path.display()
+        );
+    };
+
+    let new_paths: BTreeSet<&str> = files.iter().map(|(p, _)| p.as_str()).collect();
+    if append {
+        for entry in &existing_entries {
+            if new_paths.contains(entry.path.as_str()) && !force {
+                anyhow::bail!(
+                    "Archive `{}` already contains `{}`; pass --force to overwrite it",
+                    output.display(),
+                    entry.path
+                );
+            }
+        }
+    }
+
+    let file_count = if dedup {
+        pack_files_deduped(&mut writer, &files, compression)?
+    } else {
+        for (archive_path, disk_path) in &files {
+            writer
+                .add_file_from_disk(archive_path, disk_path)
+                .with_context(|| format!("Failed to add file `{}`", disk_path.display()))?;
+            println!("  Added: {}", archive_path);
+        }
+        files.len()
+    };
+
+    if preserve_metadata {
+        let manifest_json = serde_json::to_vec_pretty(&fs_metadata)?;
+        writer.add_file_with_compression(FS_METADATA_FILE, &manifest_json, CompressionMethod::None)?;
+        println!("  Added: {} ({} entries)", FS_METADATA_FILE, fs_metadata.len());
+    }
+
+    // Carry forward every existing entry that the new content didn't just
+    // replace; `manifest.json`/`_fs_metadata.json` are regenerated above
+    // rather than copied when this run also provides them
+    let mut carried_forward = 0;
+    for entry in &existing_entries {
+        if new_paths.contains(entry.path.as_str()) {
+            continue;
+        }
+        if entry.path == FS_METADATA_FILE && preserve_metadata {
+            continue;
+        }
+        if entry.path == "manifest.json" && manifest_path.is_some() {
+            continue;
+        }
+        writer.add_file_with_compression(&entry.path, &entry.data, entry.compression)?;
+        carried_forward += 1;
+    }
+
+    // Finalize the archive
+    writer
+        .finalize()
+        .with_context(|| format!("Failed to finalize archive `{}`", write_target.display()))?;
+
+    if append {
+        fs::rename(&write_target, &output).with_context(|| {
+            format!(
+                "Failed to replace `{}` with rebuilt archive `{}`",
+                output.display(),
+                write_target.display()
+            )
+        })?;
+        println!(
+            "Appended {} files ({} carried forward from the existing archive)",
+            file_count, carried_forward
+        );
+    } else {
+        println!("Packed {} files", file_count);
+    }
+
+    if verify {
+        verify_round_trip(&output, &files)?;
+    }
+
+    // Sign if key provided
+    if let Some(key_path) = sign_key_path {
+        println!("Signing archive...");
+        let _keypair = KeyPair::load_private(key_path)?;
+
+        // TODO: Implement signing
+        println!("Warning: Signing not yet fully implemented");
+    }
+
+    println!("Archive created successfully: {}", output.display());
+
+    Ok(())
+}
+
+/// One entry read back from an existing archive by [`read_existing_entries`]
+/// so `pack --append` can carry it forward into the rebuilt archive
+struct ExistingEntry {
+    path: String,
+    data: Vec<u8>,
+    compression: CompressionMethod,
+}
+
+/// Read every entry out of the archive at `path`, for `pack --append` to
+/// carry forward into a freshly rebuilt archive.
+///
+/// Bails if any existing entry's compression method doesn't match
+/// `requested_compression`, since `--append` has no way to store two
+/// different default codecs in one archive.
+fn read_existing_entries(path: &Path, requested_compression: CompressionMethod) -> Result<Vec<ExistingEntry>> {
+    let mut reader = ArchiveReader::open(path)
+        .with_context(|| format!("Failed to open existing archive `{}` for --append", path.display()))?;
+
+    let mut entries = Vec::new();
+    for file_path in reader.list_files().to_vec() {
+        let existing_compression = reader
+            .get_entry(&file_path)
+            .map(|e| e.compression)
+            .unwrap_or(requested_compression);
+
+        if !compression_methods_match(existing_compression, requested_compression) {
+            anyhow::bail!(
+                "--append would change the compression codec mid-archive (existing entry `{}` is {:?}, requested {:?}); pass --compression matching the existing archive",
+                file_path, existing_compression, requested_compression
+            );
+        }
+
+        let data = reader
+            .read_file(&file_path)
+            .with_context(|| format!("Failed to read existing entry `{}`", file_path))?;
+
+        entries.push(ExistingEntry {
+            path: file_path,
+            data,
+            compression: existing_compression,
+        });
+    }
+
+    Ok(entries)
+}
+
+fn compression_methods_match(a: CompressionMethod, b: CompressionMethod) -> bool {
+    matches!(
+        (a, b),
+        (CompressionMethod::None, CompressionMethod::None)
+            | (CompressionMethod::Lz4, CompressionMethod::Lz4)
+            | (CompressionMethod::Zstd, CompressionMethod::Zstd)
+    )
+}
+
+/// Chunk every file in `files`, writing only first-occurrence chunks to
+/// `writer` and recording each file's reconstruction recipe, then print the
+/// resulting dedup ratio alongside the usual compression stats.
+fn pack_files_deduped(
+    writer: &mut ArchiveWriter,
+    files: &[(String, std::path::PathBuf)],
+    compression: CompressionMethod,
+) -> Result<usize> {
+    let config = ChunkerConfig::default();
+    let mut store = ChunkStore::new();
+    let mut recipes: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let mut total_bytes = 0u64;
+
+    for (archive_path, disk_path) in files {
+        let data = fs::read(disk_path)
+            .with_context(|| format!("Failed to read file `{}`", disk_path.display()))?;
+        total_bytes += data.len() as u64;
+
+        let (recipe, newly_stored) = store.ingest_for_storage(&data, config);
+
+        for digest in newly_stored {
+            let bytes = store
+                .get_chunk(&digest)
+                .context("Newly stored chunk missing from store")?;
+            let chunk_path = format!("chunks/{}", hex::encode(digest));
+            writer.add_file_with_compression(&chunk_path, bytes, compression)?;
+        }
+
+        recipes.insert(archive_path.clone(), recipe.iter().map(hex::encode).collect());
+        println!("  Added (deduped): {}", archive_path);
+    }
+
+    let manifest_json = serde_json::to_vec_pretty(&recipes)?;
+    writer.add_file_with_compression("_dedup_manifest.json", &manifest_json, CompressionMethod::None)?;
+
+    let unique_bytes = store.unique_bytes();
+    let ratio = if unique_bytes == 0 {
+        1.0
+    } else {
+        total_bytes as f64 / unique_bytes as f64
+    };
+    println!(
+        "  Dedup: {} bytes -> {} unique bytes across {} chunks ({:.2}x)",
+        total_bytes,
+        unique_bytes,
+        store.unique_chunk_count(),
+        ratio
+    );
+
+    Ok(files.len())
+}
+
+/// `pack --list`: walk `source_path` the same way the main pack loop does
+/// and print each entry's archive path and size, without creating an
+/// archive or a writer at all.
+fn list_entries(source_path: &Path) -> Result<()> {
+    let metadata = fs::metadata(source_path)
+        .with_context(|| format!("Failed to read metadata for `{}`", source_path.display()))?;
+
+    let mut entries: Vec<(String, u64)> = Vec::new();
+
+    if metadata.is_file() {
+        let file_name = source_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .context("Invalid file name")?
+            .to_string();
+        entries.push((file_name, metadata.len()));
+    } else if metadata.is_dir() {
+        for entry in WalkDir::new(source_path)
+            .follow_links(false)
+            .sort_by_file_name()
+        {
+            let entry = entry.with_context(|| {
+                format!(
+                    "Failed to read directory entry in `{}`",
+                    source_path.display()
+                )
+            })?;
+
+            if entry.depth() == 0 || !entry.file_type().is_file() {
+                continue;
+            }
+
+            let relative_path = entry
+                .path()
+                .strip_prefix(source_path)
+                .with_context(|| {
+                    format!(
+                        "Failed to get relative path for `{}`",
+                        entry.path().display()
+                    )
+                })?
+                .to_str()
+                .context("Invalid file path")?;
+            let archive_path = normalize_path(relative_path);
+            let size = entry
+                .metadata()
+                .with_context(|| format!("Failed to read metadata for `{}`", entry.path().display()))?
+                .len();
+
+            entries.push((archive_path, size));
+        }
+    } else {
+        anyhow::bail!(
+            "Path is neither a file nor a directory: {}",
+            source_path.display()
+        );
+    }
+
+    let total: u64 = entries.iter().map(|(_, size)| size).sum();
+    for (path, size) in &entries {
+        println!("  {} ({})", path, format_size_binary(*size));
+    }
+    println!("{} across {} files", format_size_binary(total), entries.len());
+
+    Ok(())
+}
+
+/// `pack --verify`: reopen the just-written archive at `output` and
+/// byte-compare every file in `files` against its source on disk, into a
+/// scratch directory next to `output` that's always cleaned up afterward.
+fn verify_round_trip(output: &Path, files: &[(String, std::path::PathBuf)]) -> Result<()> {
+    use crate::formats::{Archive, EngramArchive};
+
+    let verify_dir = output.with_extension("eng.verify-tmp");
+    if verify_dir.exists() {
+        fs::remove_dir_all(&verify_dir).with_context(|| {
+            format!(
+                "--verify: failed to clear stale scratch directory `{}`",
+                verify_dir.display()
+            )
+        })?;
+    }
+
+    let result = (|| -> Result<()> {
+        let mut archive = EngramArchive::open(output)
+            .with_context(|| format!("--verify: failed to reopen `{}`", output.display()))?;
+        archive
+            .extract(&verify_dir, None, crate::extract::ExtractLimits::default())
+            .with_context(|| format!("--verify: failed to extract `{}`", output.display()))?;
+
+        for (archive_path, disk_path) in files {
+            let extracted_path = verify_dir.join(archive_path);
+            let extracted = fs::read(&extracted_path).with_context(|| {
+                format!(
+                    "--verify: `{}` is missing from the packed archive (expected at `{}`)",
+                    archive_path,
+                    extracted_path.display()
+                )
+            })?;
+            let original = fs::read(disk_path).with_context(|| {
+                format!(
+                    "--verify: failed to re-read source file `{}`",
+                    disk_path.display()
+                )
+            })?;
+
+            if extracted != original {
+                anyhow::bail!(
+                    "--verify: content mismatch for `{}`: archive has {} bytes, source has {} bytes",
+                    archive_path,
+                    extracted.len(),
+                    original.len()
+                );
+            }
+        }
+
+        println!("Verify: {} files round-tripped byte-for-byte", files.len());
+        Ok(())
+    })();
+
+    let _ = fs::remove_dir_all(&verify_dir);
+    result
+}
+
+/// Format a byte count using binary (1024-based) units, e.g. "1.2 MiB"
+fn format_size_binary(bytes: u64) -> String {
+    const KIB: f64 = 1024.0;
+    const MIB: f64 = KIB * 1024.0;
+    const GIB: f64 = MIB * 1024.0;
+
+    let bytes_f = bytes as f64;
+    if bytes_f < KIB {
+        format!("{bytes} B")
+    } else if bytes_f < MIB {
+        format!("{:.1} KiB", bytes_f / KIB)
+    } else if bytes_f < GIB {
+        format!("{:.1} MiB", bytes_f / MIB)
+    } else {
+        format!("{:.1} GiB", bytes_f / GIB)
+    }
+}
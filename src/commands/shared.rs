@@ -4,15 +4,35 @@
 //! like info, list, extract, verify, and search.
 
 use anyhow::{Context, Result};
+use rayon::prelude::*;
+use std::io;
 use std::path::Path;
+use std::sync::Arc;
 
+use crate::dedup;
+use crate::formats::traits::{FileEntry, FileKind, OutputFormat};
 use crate::formats::{
     detect_format, Archive, ArchiveFormat, CartridgeArchive, DataCardArchive, DataSpoolArchive,
-    EngramArchive, MutableArchive, QueryableArchive,
+    EngramArchive, MutableArchive, QueryableArchive, TarArchive, ZipArchive,
 };
 
 /// Dispatch info command to the appropriate format handler
-pub fn info(path: &Path, inspect: bool, verify_sigs: bool, show_manifest: bool) -> Result<()> {
+///
+/// `password` comes from [`crate::crypto::password::resolve_optional`] and
+/// is accepted here so `--password-file`/`STRATEGOS_PASSWORD` are already
+/// wired up, but no format in this build yet reports itself as encrypted,
+/// so it currently has nothing to unlock.
+pub fn info(
+    path: &Path,
+    inspect: bool,
+    verify_sigs: bool,
+    show_manifest: bool,
+    password: Option<String>,
+) -> Result<()> {
+    if password.is_some() {
+        println!("Warning: password provided, but no archive format in this build reports itself encrypted yet");
+    }
+
     let format = detect_format(path)?;
 
     match format {
@@ -24,7 +44,7 @@ pub fn info(path: &Path, inspect: bool, verify_sigs: bool, show_manifest: bool)
             let mut archive = CartridgeArchive::open(path)?;
             display_info(&mut archive, inspect, verify_sigs, show_manifest)
         }
-        ArchiveFormat::DataSpool => {
+        ArchiveFormat::DataSpool | ArchiveFormat::DataSpoolSplit => {
             let mut archive = DataSpoolArchive::open(path)?;
             display_info(&mut archive, inspect, verify_sigs, show_manifest)
         }
@@ -32,6 +52,14 @@ pub fn info(path: &Path, inspect: bool, verify_sigs: bool, show_manifest: bool)
             let mut archive = DataCardArchive::open(path)?;
             display_info(&mut archive, inspect, verify_sigs, show_manifest)
         }
+        ArchiveFormat::Tar => {
+            let mut archive = TarArchive::open(path)?;
+            display_info(&mut archive, inspect, verify_sigs, show_manifest)
+        }
+        ArchiveFormat::Zip => {
+            let mut archive = ZipArchive::open(path)?;
+            display_info(&mut archive, inspect, verify_sigs, show_manifest)
+        }
         ArchiveFormat::Unknown => {
             anyhow::bail!("Unknown archive format: {}", path.display())
         }
@@ -75,7 +103,11 @@ fn display_info<A: Archive>(
         println!("\n📋 Files:");
         let files = archive.list_files()?;
         for (i, file) in files.iter().take(10).enumerate() {
-            println!("  {}: {} ({} bytes)", i + 1, file.path, file.size);
+            if let Err(e) = crate::extract::validate_path_components(&file.path) {
+                println!("  {}: {} ⚠️  {}", i + 1, file.path, e);
+            } else {
+                println!("  {}: {} ({} bytes)", i + 1, file.path, file.size);
+            }
         }
         if files.len() > 10 {
             println!("  ... and {} more", files.len() - 10);
@@ -86,7 +118,19 @@ fn display_info<A: Archive>(
 }
 
 /// Dispatch list command to the appropriate format handler
-pub fn list(path: &Path, long_format: bool, databases_only: bool) -> Result<()> {
+///
+/// `password` comes from [`crate::crypto::password::resolve_optional`]; see
+/// [`info`] for why it's currently accepted but unused.
+pub fn list(
+    path: &Path,
+    long_format: bool,
+    databases_only: bool,
+    password: Option<String>,
+) -> Result<()> {
+    if password.is_some() {
+        println!("Warning: password provided, but no archive format in this build reports itself encrypted yet");
+    }
+
     let format = detect_format(path)?;
 
     match format {
@@ -98,7 +142,7 @@ pub fn list(path: &Path, long_format: bool, databases_only: bool) -> Result<()>
             let mut archive = CartridgeArchive::open(path)?;
             display_list(&mut archive, long_format, databases_only)
         }
-        ArchiveFormat::DataSpool => {
+        ArchiveFormat::DataSpool | ArchiveFormat::DataSpoolSplit => {
             let mut archive = DataSpoolArchive::open(path)?;
             display_list(&mut archive, long_format, databases_only)
         }
@@ -106,6 +150,14 @@ pub fn list(path: &Path, long_format: bool, databases_only: bool) -> Result<()>
             let mut archive = DataCardArchive::open(path)?;
             display_list(&mut archive, long_format, databases_only)
         }
+        ArchiveFormat::Tar => {
+            let mut archive = TarArchive::open(path)?;
+            display_list(&mut archive, long_format, databases_only)
+        }
+        ArchiveFormat::Zip => {
+            let mut archive = ZipArchive::open(path)?;
+            display_list(&mut archive, long_format, databases_only)
+        }
         ArchiveFormat::Unknown => {
             anyhow::bail!("Unknown archive format: {}", path.display())
         }
@@ -134,7 +186,7 @@ fn display_list<A: Archive>(
         for file in filtered {
             println!(
                 "{:<50} {:>12} {:>12} {:>10}",
-                file.path,
+                unsafe_marker(&file.path),
                 format_size(file.size),
                 format_size(file.compressed_size),
                 file.compression_method
@@ -142,7 +194,7 @@ fn display_list<A: Archive>(
         }
     } else {
         for file in filtered {
-            println!("{}", file.path);
+            println!("{}", unsafe_marker(&file.path));
         }
     }
 
@@ -150,25 +202,86 @@ fn display_list<A: Archive>(
 }
 
 /// Dispatch extract command to the appropriate format handler
-pub fn extract(archive_path: &Path, output: &Path, files: Option<Vec<String>>) -> Result<()> {
+///
+/// `jobs` is the number of worker threads to extract with; 1 (the default)
+/// runs the format's own single-threaded `extract`, anything higher routes
+/// through [`extract_parallel`] instead. `output` is only required when
+/// `to_stdout` is false; the CLI layer enforces that with
+/// `required_unless_present`.
+///
+/// `password` is resolved by the caller (see [`crate::crypto::password`])
+/// whenever `--decrypt` is set; archive decryption isn't wired up yet, so
+/// it's only captured here in preparation for that.
+///
+/// When `to_stdout` is set, `files` must resolve to exactly one entry,
+/// whose decompressed bytes are streamed to a locked stdout handle instead
+/// of anything touching the filesystem; every status message this function
+/// would otherwise print goes to stderr instead, so the piped stdout
+/// stream carries only the entry's content.
+pub fn extract(
+    archive_path: &Path,
+    output: Option<&Path>,
+    files: Option<Vec<String>>,
+    limits: crate::extract::ExtractLimits,
+    jobs: usize,
+    password: Option<String>,
+    to_stdout: bool,
+) -> Result<()> {
+    if password.is_some() {
+        eprintln!("Warning: Decryption not yet implemented in this version (password captured but unused)");
+    }
+
+    if to_stdout {
+        let selected = match files.as_deref() {
+            Some([single]) => single,
+            _ => anyhow::bail!("--stdout requires --files to select exactly one entry"),
+        };
+
+        let format = detect_format(archive_path)?;
+        let data = read_entry(format, archive_path, selected)?;
+
+        use std::io::Write;
+        io::stdout()
+            .lock()
+            .write_all(&data)
+            .context("Failed to write entry to stdout")?;
+        return Ok(());
+    }
+
+    let output = output.context("Output directory is required unless --stdout is set")?;
+
+    if jobs > 1 {
+        extract_parallel(archive_path, output, files.as_deref(), limits, jobs)?;
+        println!("✅ Extracted to: {}", output.display());
+        return Ok(());
+    }
+
     let format = detect_format(archive_path)?;
 
     match format {
         ArchiveFormat::Engram => {
             let mut archive = EngramArchive::open(archive_path)?;
-            archive.extract(output, files.as_deref())?;
+            archive.extract(output, files.as_deref(), limits)?;
         }
         ArchiveFormat::Cartridge => {
             let mut archive = CartridgeArchive::open(archive_path)?;
-            archive.extract(output, files.as_deref())?;
+            archive.extract(output, files.as_deref(), limits)?;
         }
-        ArchiveFormat::DataSpool => {
+        ArchiveFormat::DataSpool | ArchiveFormat::DataSpoolSplit => {
             let mut archive = DataSpoolArchive::open(archive_path)?;
-            archive.extract(output, files.as_deref())?;
+            archive.extract(output, files.as_deref(), limits)?;
         }
         ArchiveFormat::DataCard => {
             let mut archive = DataCardArchive::open(archive_path)?;
-            archive.extract(output, files.as_deref())?;
+            archive.extract(output, files.as_deref(), limits)?;
+        }
+        ArchiveFormat::Tar => {
+            let mut archive = TarArchive::open(archive_path)?;
+            archive.extract(output, files.as_deref(), limits)?;
+        }
+        ArchiveFormat::Zip => {
+            let mut archive = ZipArchive::open(archive_path)?;
+            archive.extract(output, files.as_deref(), limits)?;
         }
         ArchiveFormat::Unknown => {
             anyhow::bail!("Unknown archive format: {}", archive_path.display())
@@ -179,6 +292,162 @@ pub fn extract(archive_path: &Path, output: &Path, files: Option<Vec<String>>) -
     Ok(())
 }
 
+/// Extract `archive_path` with `jobs` worker threads instead of one.
+///
+/// Entry discovery, and any non-regular node (directories, symlinks,
+/// device/fifo nodes — rare, and order-sensitive, since a directory has to
+/// exist before anything extracts into it), run single-threaded up front.
+/// Regular files, which are normally the bulk of both the entry count and
+/// the bytes moved, are what get fanned out across a rayon thread pool:
+/// each worker re-opens its own archive handle and pulls one entry's
+/// content independently rather than sharing a reader, and every worker
+/// charges the same [`crate::extract::AtomicExtractGuard`] so the
+/// configured size/file ceilings hold regardless of thread interleaving.
+///
+/// Note this goes through `list_files()` rather than each format's own
+/// `extract()`, so it only sees what that already surfaces — an Engram
+/// archive's `_fs_metadata.json`-described symlinks/devices (see
+/// `crate::commands::pack`) aren't visible here and won't be recreated;
+/// use the single-threaded path (`jobs` left at 1) for those.
+fn extract_parallel(
+    archive_path: &Path,
+    output: &Path,
+    files: Option<&[String]>,
+    limits: crate::extract::ExtractLimits,
+    jobs: usize,
+) -> Result<()> {
+    let format = detect_format(archive_path)?;
+    let entries = list_entries(format, archive_path)?;
+
+    let wanted: Vec<FileEntry> = match files {
+        Some(selected) => entries
+            .into_iter()
+            .filter(|e| selected.iter().any(|f| f == &e.path))
+            .collect(),
+        None => entries,
+    };
+
+    let guard = Arc::new(crate::extract::AtomicExtractGuard::new(output, limits)?);
+
+    let (regular, special): (Vec<FileEntry>, Vec<FileEntry>) =
+        wanted.into_iter().partition(|e| e.kind == FileKind::Regular);
+
+    for entry in &special {
+        extract_one(format, archive_path, &guard, entry)?;
+    }
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(jobs)
+        .build()
+        .context("Failed to build extraction thread pool")?;
+
+    pool.install(|| {
+        regular
+            .par_iter()
+            .try_for_each(|entry| extract_one(format, archive_path, &guard, entry))
+    })?;
+
+    Ok(())
+}
+
+/// List every entry in `archive_path`, opening just enough of the archive
+/// to ask it
+fn list_entries(format: ArchiveFormat, archive_path: &Path) -> Result<Vec<FileEntry>> {
+    match format {
+        ArchiveFormat::Engram => EngramArchive::open(archive_path)?.list_files(),
+        ArchiveFormat::Cartridge => CartridgeArchive::open(archive_path)?.list_files(),
+        ArchiveFormat::DataSpool | ArchiveFormat::DataSpoolSplit => {
+            DataSpoolArchive::open(archive_path)?.list_files()
+        }
+        ArchiveFormat::DataCard => DataCardArchive::open(archive_path)?.list_files(),
+        ArchiveFormat::Tar => TarArchive::open(archive_path)?.list_files(),
+        ArchiveFormat::Zip => ZipArchive::open(archive_path)?.list_files(),
+        ArchiveFormat::Unknown => anyhow::bail!("Unknown archive format: {}", archive_path.display()),
+    }
+}
+
+/// Read one file's content out of `archive_path` through a fresh archive
+/// handle, so concurrent workers never share one
+fn read_entry(format: ArchiveFormat, archive_path: &Path, path: &str) -> Result<Vec<u8>> {
+    match format {
+        ArchiveFormat::Engram => EngramArchive::open(archive_path)?.read_file(path),
+        ArchiveFormat::Cartridge => CartridgeArchive::open(archive_path)?.read_file(path),
+        ArchiveFormat::DataSpool | ArchiveFormat::DataSpoolSplit => {
+            DataSpoolArchive::open(archive_path)?.read_file(path)
+        }
+        ArchiveFormat::DataCard => DataCardArchive::open(archive_path)?.read_file(path),
+        ArchiveFormat::Tar => TarArchive::open(archive_path)?.read_file(path),
+        ArchiveFormat::Zip => ZipArchive::open(archive_path)?.read_file(path),
+        ArchiveFormat::Unknown => anyhow::bail!("Unknown archive format: {}", archive_path.display()),
+    }
+}
+
+/// Resolve and charge one entry against `guard`, then recreate it on disk
+/// according to its `kind`
+fn extract_one(
+    format: ArchiveFormat,
+    archive_path: &Path,
+    guard: &crate::extract::AtomicExtractGuard,
+    entry: &FileEntry,
+) -> Result<()> {
+    match &entry.kind {
+        FileKind::Directory => {
+            guard.charge(0)?;
+            let output_path = guard.resolve(&entry.path)?;
+            std::fs::create_dir_all(&output_path)?;
+            guard.charge_written(0)?;
+        }
+        FileKind::Symlink { target } => {
+            guard.charge(0)?;
+            let output_path = guard.resolve(&entry.path)?;
+            guard.validate_symlink_target(&output_path, target)?;
+            let _ = std::fs::remove_file(&output_path);
+            std::os::unix::fs::symlink(target, &output_path)
+                .with_context(|| format!("Failed to create symlink: {}", output_path.display()))?;
+            guard.charge_written(0)?;
+        }
+        FileKind::CharDevice { major, minor } | FileKind::BlockDevice { major, minor } => {
+            guard.charge(0)?;
+            let output_path = guard.resolve(&entry.path)?;
+            crate::formats::unix_meta::mknod(&output_path, &entry.kind, *major, *minor)?;
+            guard.charge_written(0)?;
+        }
+        FileKind::Fifo => {
+            guard.charge(0)?;
+            let output_path = guard.resolve(&entry.path)?;
+            crate::formats::unix_meta::mkfifo(&output_path)?;
+            guard.charge_written(0)?;
+        }
+        FileKind::Regular => {
+            let data = read_entry(format, archive_path, &entry.path)?;
+            guard.charge(data.len() as u64)?;
+            let output_path = guard.resolve(&entry.path)?;
+            std::fs::write(&output_path, &data)
+                .with_context(|| format!("Failed to write file: {}", output_path.display()))?;
+            guard.charge_written(data.len() as u64)?;
+        }
+    }
+
+    crate::formats::unix_meta::apply(
+        &guard.resolve(&entry.path)?,
+        entry.mode,
+        entry.uid,
+        entry.gid,
+        &entry.xattrs,
+        &entry.kind,
+    )
+}
+
+/// Mark an entry's display path with a warning if it would fail
+/// [`crate::extract::validate_path_components`], so `list` surfaces the same
+/// unsafe paths `extract` would refuse without actually extracting anything
+fn unsafe_marker(path: &str) -> String {
+    match crate::extract::validate_path_components(path) {
+        Ok(()) => path.to_string(),
+        Err(e) => format!("{} ⚠️  {}", path, e),
+    }
+}
+
 /// Dispatch verify command to the appropriate format handler
 pub fn verify(path: &Path) -> Result<()> {
     let format = detect_format(path)?;
@@ -192,7 +461,7 @@ pub fn verify(path: &Path) -> Result<()> {
             let mut archive = CartridgeArchive::open(path)?;
             archive.verify()?
         }
-        ArchiveFormat::DataSpool => {
+        ArchiveFormat::DataSpool | ArchiveFormat::DataSpoolSplit => {
             let mut archive = DataSpoolArchive::open(path)?;
             archive.verify()?
         }
@@ -200,6 +469,14 @@ pub fn verify(path: &Path) -> Result<()> {
             let mut archive = DataCardArchive::open(path)?;
             archive.verify()?
         }
+        ArchiveFormat::Tar => {
+            let mut archive = TarArchive::open(path)?;
+            archive.verify()?
+        }
+        ArchiveFormat::Zip => {
+            let mut archive = ZipArchive::open(path)?;
+            archive.verify()?
+        }
         ArchiveFormat::Unknown => {
             anyhow::bail!("Unknown archive format: {}", path.display())
         }
@@ -213,48 +490,212 @@ pub fn verify(path: &Path) -> Result<()> {
     }
 }
 
+/// Resolve each manifest signature's signer key-id through `resolver` and
+/// report whether the embedded key matches what a trusted source says it
+/// should be. Only Engram archives carry manifest signatures.
+pub fn verify_signers(
+    archive_path: &Path,
+    resolver: &dyn crate::crypto::resolver::KeyResolver,
+) -> Result<()> {
+    let format = detect_format(archive_path)?;
+    if !matches!(format, ArchiveFormat::Engram) {
+        anyhow::bail!("Signer resolution is only supported for Engram archives");
+    }
+
+    let mut archive = EngramArchive::open(archive_path)?;
+    let results = archive.verify_signers(resolver)?;
+
+    if results.is_empty() {
+        println!("No signatures to resolve");
+        return Ok(());
+    }
+
+    for result in results {
+        let signer = result.signer.as_deref().unwrap_or("(anonymous)");
+        print!("Signer: {} — manifest signature {}", signer, if result.manifest_valid { "valid" } else { "INVALID" });
+
+        match (&result.resolution, result.resolved_key_matches_embedded) {
+            (Some(resolved), Some(true)) => {
+                println!(", resolved key matches ({})", resolved.source);
+            }
+            (Some(resolved), Some(false)) => {
+                println!(", ⚠️  resolved key DOES NOT MATCH embedded key ({})", resolved.source);
+            }
+            (Some(_), None) | (None, _) => {
+                println!(", could not resolve a trusted key for this signer");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Report content-defined deduplication statistics for an archive
+pub fn dups(archive_path: &Path, format: &str) -> Result<()> {
+    let output_format = match format {
+        "json" => OutputFormat::Json,
+        "csv" => OutputFormat::Csv,
+        _ => OutputFormat::Table,
+    };
+
+    let detected = detect_format(archive_path)?;
+    let report = match detected {
+        ArchiveFormat::Engram => {
+            dedup::analyze(&mut EngramArchive::open(archive_path)?, Default::default())?
+        }
+        ArchiveFormat::Cartridge => {
+            dedup::analyze(&mut CartridgeArchive::open(archive_path)?, Default::default())?
+        }
+        ArchiveFormat::DataSpool | ArchiveFormat::DataSpoolSplit => {
+            dedup::analyze(&mut DataSpoolArchive::open(archive_path)?, Default::default())?
+        }
+        ArchiveFormat::DataCard => {
+            dedup::analyze(&mut DataCardArchive::open(archive_path)?, Default::default())?
+        }
+        ArchiveFormat::Tar | ArchiveFormat::Zip => {
+            anyhow::bail!("{} does not support dups", detected.name())
+        }
+        ArchiveFormat::Unknown => {
+            anyhow::bail!("Unknown archive format: {}", archive_path.display())
+        }
+    };
+
+    println!("{}", dedup::format_report(&report, output_format)?);
+    Ok(())
+}
+
+/// Stream a single archive entry directly to stdout
+pub fn pipe(archive_path: &Path, file: &str) -> Result<()> {
+    let format = detect_format(archive_path)?;
+    let mut stdout = std::io::stdout().lock();
+
+    match format {
+        ArchiveFormat::Engram => {
+            EngramArchive::open(archive_path)?.read_file_to(file, &mut stdout)?
+        }
+        ArchiveFormat::Cartridge => {
+            CartridgeArchive::open(archive_path)?.read_file_to(file, &mut stdout)?
+        }
+        ArchiveFormat::DataSpool | ArchiveFormat::DataSpoolSplit => {
+            DataSpoolArchive::open(archive_path)?.read_file_to(file, &mut stdout)?
+        }
+        ArchiveFormat::DataCard => {
+            DataCardArchive::open(archive_path)?.read_file_to(file, &mut stdout)?
+        }
+        ArchiveFormat::Tar | ArchiveFormat::Zip => {
+            anyhow::bail!("{} does not support pipe", format.name())
+        }
+        ArchiveFormat::Unknown => {
+            anyhow::bail!("Unknown archive format: {}", archive_path.display())
+        }
+    };
+
+    Ok(())
+}
+
+/// Mount an archive as a read-only filesystem at `mountpoint`
+pub fn mount(archive_path: &Path, mountpoint: &Path) -> Result<()> {
+    let format = detect_format(archive_path)?;
+
+    let archive: Box<dyn Archive> = match format {
+        ArchiveFormat::Engram => Box::new(EngramArchive::open(archive_path)?),
+        ArchiveFormat::Cartridge => Box::new(CartridgeArchive::open(archive_path)?),
+        ArchiveFormat::DataSpool | ArchiveFormat::DataSpoolSplit => {
+            Box::new(DataSpoolArchive::open(archive_path)?)
+        }
+        ArchiveFormat::DataCard => Box::new(DataCardArchive::open(archive_path)?),
+        ArchiveFormat::Tar | ArchiveFormat::Zip => {
+            anyhow::bail!("{} does not support mount", format.name())
+        }
+        ArchiveFormat::Unknown => {
+            anyhow::bail!("Unknown archive format: {}", archive_path.display())
+        }
+    };
+
+    println!("Mounting {} at {}", archive_path.display(), mountpoint.display());
+    crate::mount::mount(archive, mountpoint)
+}
+
 /// Dispatch search command to the appropriate format handler
-pub fn search(path: &Path, pattern: &str, case_insensitive: bool) -> Result<()> {
+///
+/// Scans via [`crate::search::parallel_search`] so large archives are split
+/// across a worker pool instead of walked on a single thread.
+pub fn search(
+    path: &Path,
+    pattern: &str,
+    options: &crate::search::SearchOptions,
+    json: bool,
+) -> Result<()> {
     let format = detect_format(path)?;
 
     let results = match format {
-        ArchiveFormat::Engram => {
-            let mut archive = EngramArchive::open(path)?;
-            archive.search(pattern, case_insensitive)?
-        }
+        ArchiveFormat::Engram => crate::search::parallel_search::<EngramArchive>(path, pattern, options)?,
         ArchiveFormat::Cartridge => {
-            let mut archive = CartridgeArchive::open(path)?;
-            archive.search(pattern, case_insensitive)?
+            crate::search::parallel_search::<CartridgeArchive>(path, pattern, options)?
         }
-        ArchiveFormat::DataSpool => {
-            let mut archive = DataSpoolArchive::open(path)?;
-            archive.search(pattern, case_insensitive)?
+        ArchiveFormat::DataSpool | ArchiveFormat::DataSpoolSplit => {
+            crate::search::parallel_search::<DataSpoolArchive>(path, pattern, options)?
         }
         ArchiveFormat::DataCard => {
-            let mut archive = DataCardArchive::open(path)?;
-            archive.search(pattern, case_insensitive)?
+            crate::search::parallel_search::<DataCardArchive>(path, pattern, options)?
         }
+        ArchiveFormat::Tar => crate::search::parallel_search::<TarArchive>(path, pattern, options)?,
+        ArchiveFormat::Zip => crate::search::parallel_search::<ZipArchive>(path, pattern, options)?,
         ArchiveFormat::Unknown => {
             anyhow::bail!("Unknown archive format: {}", path.display())
         }
     };
 
+    print_results(&results, json);
+    Ok(())
+}
+
+/// Render search results either as plain text or as structured JSON
+/// (file/line/column/match spans) for tooling to consume.
+pub(crate) fn print_results(results: &[crate::formats::traits::SearchResult], json: bool) {
+    if json {
+        let json_results: Vec<_> = results
+            .iter()
+            .map(|r| {
+                serde_json::json!({
+                    "file": r.file_path,
+                    "line": r.line_number,
+                    "content": r.line_content,
+                    "matches": r.match_spans.iter().map(|(start, end)| serde_json::json!({
+                        "column": start + 1,
+                        "start": start,
+                        "end": end,
+                    })).collect::<Vec<_>>(),
+                    "before": r.before,
+                    "after": r.after,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&json_results).unwrap_or_default());
+        return;
+    }
+
     if results.is_empty() {
         println!("No matches found");
-    } else {
-        println!("Found {} matches:\n", results.len());
-        for result in results.iter().take(100) {
-            println!(
-                "{}:{}:{}",
-                result.file_path, result.line_number, result.line_content
-            );
-        }
-        if results.len() > 100 {
-            println!("\n... and {} more matches", results.len() - 100);
-        }
+        return;
     }
 
-    Ok(())
+    println!("Found {} matches:\n", results.len());
+    for result in results.iter().take(100) {
+        for line in &result.before {
+            println!("{}-{}-{}", result.file_path, result.line_number, line);
+        }
+        println!(
+            "{}:{}:{}",
+            result.file_path, result.line_number, result.line_content
+        );
+        for line in &result.after {
+            println!("{}-{}-{}", result.file_path, result.line_number, line);
+        }
+    }
+    if results.len() > 100 {
+        println!("\n... and {} more matches", results.len() - 100);
+    }
 }
 
 /// Format byte size for display
@@ -0,0 +1,118 @@
+//! ASCII-armored encoding for key material
+//!
+//! Wraps an arbitrary byte blob (a raw public key, or a [`super::keys`]
+//! private-key container) in a labeled, self-describing text block so keys
+//! can round-trip cleanly through email bodies, chat, and other channels
+//! that mangle or strip raw binary. Modeled on the classic PGP armor shape:
+//! a `BEGIN`/`END` banner naming the payload, base64 wrapped at 64 columns,
+//! and a trailing checksum line so truncation or corruption is caught
+//! before the payload is ever handed to the cipher/signature code.
+
+use anyhow::{Context, Result};
+use base64::Engine;
+
+const LINE_WIDTH: usize = 64;
+
+/// Base64-encode `data` under a `-----BEGIN STRATEGOS {label}-----` banner,
+/// with a blake3-derived checksum line before the footer.
+pub fn encode(label: &str, data: &[u8]) -> String {
+    let body = base64::engine::general_purpose::STANDARD.encode(data);
+    let checksum = checksum_line(data);
+
+    let mut out = String::new();
+    out.push_str(&format!("-----BEGIN STRATEGOS {}-----\n", label));
+    for chunk in body.as_bytes().chunks(LINE_WIDTH) {
+        out.push_str(std::str::from_utf8(chunk).expect("base64 output is ASCII"));
+        out.push('\n');
+    }
+    out.push_str(&format!("={}\n", checksum));
+    out.push_str(&format!("-----END STRATEGOS {}-----\n", label));
+    out
+}
+
+/// Parse an armored block produced by [`encode`], requiring its label to
+/// match `expected_label` and its checksum to match the decoded payload.
+pub fn decode(expected_label: &str, armored: &str) -> Result<Vec<u8>> {
+    let begin = format!("-----BEGIN STRATEGOS {}-----", expected_label);
+    let end = format!("-----END STRATEGOS {}-----", expected_label);
+
+    let start = armored
+        .find(&begin)
+        .with_context(|| format!("Missing '{}' banner", begin))?
+        + begin.len();
+    let stop = armored[start..]
+        .find(&end)
+        .with_context(|| format!("Missing '{}' banner", end))?
+        + start;
+
+    let mut checksum = None;
+    let mut body = String::new();
+    for line in armored[start..stop].lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(sum) = line.strip_prefix('=') {
+            checksum = Some(sum.to_string());
+            continue;
+        }
+        body.push_str(line);
+    }
+
+    let data = base64::engine::general_purpose::STANDARD
+        .decode(body)
+        .context("Invalid base64 in armored block")?;
+
+    if let Some(expected) = checksum {
+        let actual = checksum_line(&data);
+        if actual != expected {
+            anyhow::bail!(
+                "Armor checksum mismatch: expected {}, got {} (block is corrupted or truncated)",
+                expected,
+                actual
+            );
+        }
+    }
+
+    Ok(data)
+}
+
+/// Whether `text` looks like one of our armored blocks, for auto-detecting
+/// armored vs. raw hex key files without requiring the caller to know ahead
+/// of time which encoding a given key file uses.
+pub fn looks_armored(text: &str) -> bool {
+    text.trim_start().starts_with("-----BEGIN STRATEGOS ")
+}
+
+/// Short checksum over `data`, printed as 8 hex characters
+fn checksum_line(data: &[u8]) -> String {
+    hex::encode(&blake3::hash(data).as_bytes()[..4])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let data = b"super secret key bytes go here!";
+        let armored = encode("TEST KEY", data);
+        assert!(looks_armored(&armored));
+
+        let decoded = decode("TEST KEY", &armored).unwrap();
+        assert_eq!(decoded, data);
+    }
+
+    #[test]
+    fn test_wrong_label_rejected() {
+        let armored = encode("PUBLIC KEY", b"data");
+        assert!(decode("PRIVATE KEY", &armored).is_err());
+    }
+
+    #[test]
+    fn test_corrupted_checksum_rejected() {
+        let mut armored = encode("TEST KEY", b"some key bytes");
+        armored = armored.replace("-----END", "tampered\n-----END");
+        assert!(decode("TEST KEY", &armored).is_err());
+    }
+}
@@ -3,11 +3,32 @@
 //! Handles key generation, loading, and saving for signing Engram archives.
 
 use anyhow::{Context, Result};
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
 use ed25519_dalek::{PUBLIC_KEY_LENGTH, SECRET_KEY_LENGTH, SigningKey, VerifyingKey};
 use rand::rngs::OsRng;
+use rand::RngCore;
 use std::fs;
 use std::path::Path;
 
+use super::armor;
+
+/// Container magic for passphrase-encrypted private keys; chosen so it can
+/// never be mistaken for the start of a hex-encoded legacy key file.
+const MAGIC: &[u8; 4] = b"SSK1";
+const CONTAINER_VERSION: u8 = 1;
+const KDF_ARGON2ID: u8 = 1;
+const CIPHER_CHACHA20POLY1305: u8 = 1;
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+// OWASP-recommended Argon2id defaults (19 MiB, 2 passes, 1 lane).
+const ARGON2_M_COST_KIB: u32 = 19 * 1024;
+const ARGON2_T_COST: u32 = 2;
+const ARGON2_P_COST: u32 = 1;
+
 /// Ed25519 keypair for signing engrams
 pub struct KeyPair {
     signing_key: SigningKey,
@@ -71,12 +92,221 @@ impl KeyPair {
 
         Ok(())
     }
+
+    /// Save the keypair with the private key sealed under `passphrase`
+    ///
+    /// The public key is still written as plain hex, since it isn't secret.
+    pub fn save_encrypted(
+        &self,
+        private_path: impl AsRef<Path>,
+        public_path: impl AsRef<Path>,
+        passphrase: &str,
+    ) -> Result<()> {
+        let container = seal_secret_key(&self.signing_key.to_bytes(), passphrase)?;
+        fs::write(private_path, container).context("Failed to write encrypted private key")?;
+
+        let public_hex = hex::encode(self.verifying_key().to_bytes());
+        fs::write(public_path, public_hex).context("Failed to write public key")?;
+
+        Ok(())
+    }
+
+    /// Load a private key file that may be either passphrase-encrypted or
+    /// legacy plaintext hex, detected from the container magic bytes
+    pub fn load_private_encrypted(path: impl AsRef<Path>, passphrase: &str) -> Result<Self> {
+        let raw = fs::read(&path).context("Failed to read private key file")?;
+
+        if raw.len() >= MAGIC.len() && raw[..MAGIC.len()] == *MAGIC {
+            let key_bytes = open_secret_key(&raw, passphrase)?;
+            return Self::from_bytes(&key_bytes);
+        }
+
+        // No magic bytes: this predates encryption support, load it as
+        // plaintext hex the same way `load_private` always has.
+        Self::load_private(path)
+    }
+
+    /// Export the public key as an ASCII-armored block, for sharing through
+    /// email or chat rather than shipping a raw hex file
+    pub fn export_public_armored(&self) -> String {
+        export_public_key_armored(&self.verifying_key())
+    }
+
+    /// Export the private key, sealed under `passphrase`, as an
+    /// ASCII-armored block so it can travel through text-only channels
+    pub fn export_private_armored(&self, passphrase: &str) -> Result<String> {
+        let container = seal_secret_key(&self.signing_key.to_bytes(), passphrase)?;
+        Ok(armor::encode(PRIVATE_KEY_LABEL, &container))
+    }
+
+    /// Import a private key previously exported with
+    /// [`KeyPair::export_private_armored`]
+    pub fn import_private_armored(armored: &str, passphrase: &str) -> Result<Self> {
+        let container = armor::decode(PRIVATE_KEY_LABEL, armored)?;
+        let key_bytes = open_secret_key(&container, passphrase)?;
+        Self::from_bytes(&key_bytes)
+    }
 }
 
-/// Load public key from file (hex-encoded)
-pub fn load_public_key(path: impl AsRef<Path>) -> Result<VerifyingKey> {
-    let hex_str = fs::read_to_string(path).context("Failed to read public key file")?;
-    let bytes = hex::decode(hex_str.trim()).context("Invalid hex encoding")?;
+/// Import a public key previously exported with
+/// [`KeyPair::export_public_armored`]
+pub fn import_public_key_armored(armored: &str) -> Result<VerifyingKey> {
+    let bytes = armor::decode(PUBLIC_KEY_LABEL, armored)?;
+    if bytes.len() != PUBLIC_KEY_LENGTH {
+        anyhow::bail!(
+            "Invalid public key length: expected {}, got {}",
+            PUBLIC_KEY_LENGTH,
+            bytes.len()
+        );
+    }
+    let mut key_bytes = [0u8; PUBLIC_KEY_LENGTH];
+    key_bytes.copy_from_slice(&bytes);
+    Ok(VerifyingKey::from_bytes(&key_bytes)?)
+}
+
+/// Seal a 32-byte Ed25519 secret key into the self-describing container
+/// format parsed by [`open_secret_key`]
+///
+/// Layout: `MAGIC | version | kdf_tag | m_cost | t_cost | p_cost | salt |
+/// cipher_tag | nonce | ciphertext_with_tag` (all integers little-endian).
+fn seal_secret_key(secret: &[u8; SECRET_KEY_LENGTH], passphrase: &str) -> Result<Vec<u8>> {
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+
+    let wrapping_key = derive_wrapping_key(
+        passphrase,
+        &salt,
+        ARGON2_M_COST_KIB,
+        ARGON2_T_COST,
+        ARGON2_P_COST,
+    )?;
+
+    let mut nonce_bytes = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce_bytes);
+
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(&wrapping_key));
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce_bytes), secret.as_slice())
+        .map_err(|_| anyhow::anyhow!("Failed to seal private key"))?;
+
+    let mut out = Vec::with_capacity(
+        MAGIC.len() + 2 + 12 + SALT_LEN + 1 + NONCE_LEN + ciphertext.len(),
+    );
+    out.extend_from_slice(MAGIC);
+    out.push(CONTAINER_VERSION);
+    out.push(KDF_ARGON2ID);
+    out.extend_from_slice(&ARGON2_M_COST_KIB.to_le_bytes());
+    out.extend_from_slice(&ARGON2_T_COST.to_le_bytes());
+    out.extend_from_slice(&ARGON2_P_COST.to_le_bytes());
+    out.extend_from_slice(&salt);
+    out.push(CIPHER_CHACHA20POLY1305);
+    out.extend_from_slice(&nonce_bytes);
+    out.extend_from_slice(&ciphertext);
+
+    Ok(out)
+}
+
+/// Parse and open a container produced by [`seal_secret_key`]
+///
+/// Distinguishes a malformed/unsupported container (bad magic, version,
+/// length) from a wrong passphrase (AEAD tag mismatch), since only the
+/// latter should ever be ambiguous to the caller.
+fn open_secret_key(raw: &[u8], passphrase: &str) -> Result<[u8; SECRET_KEY_LENGTH]> {
+    let header_len = MAGIC.len() + 2 + 12 + SALT_LEN + 1 + NONCE_LEN;
+    if raw.len() < header_len {
+        anyhow::bail!("Encrypted key file is truncated");
+    }
+
+    let mut cursor = MAGIC.len();
+
+    let version = raw[cursor];
+    cursor += 1;
+    if version != CONTAINER_VERSION {
+        anyhow::bail!("Unsupported encrypted key container version: {}", version);
+    }
+
+    let kdf_tag = raw[cursor];
+    cursor += 1;
+    if kdf_tag != KDF_ARGON2ID {
+        anyhow::bail!("Unsupported KDF tag: {}", kdf_tag);
+    }
+
+    let m_cost = u32::from_le_bytes(raw[cursor..cursor + 4].try_into().unwrap());
+    cursor += 4;
+    let t_cost = u32::from_le_bytes(raw[cursor..cursor + 4].try_into().unwrap());
+    cursor += 4;
+    let p_cost = u32::from_le_bytes(raw[cursor..cursor + 4].try_into().unwrap());
+    cursor += 4;
+
+    let salt = &raw[cursor..cursor + SALT_LEN];
+    cursor += SALT_LEN;
+
+    let cipher_tag = raw[cursor];
+    cursor += 1;
+    if cipher_tag != CIPHER_CHACHA20POLY1305 {
+        anyhow::bail!("Unsupported cipher tag: {}", cipher_tag);
+    }
+
+    let nonce_bytes = &raw[cursor..cursor + NONCE_LEN];
+    cursor += NONCE_LEN;
+    let ciphertext = &raw[cursor..];
+
+    let wrapping_key = derive_wrapping_key(passphrase, salt, m_cost, t_cost, p_cost)?;
+
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(&wrapping_key));
+    let plaintext = cipher
+        .decrypt(Nonce::from_slice(nonce_bytes), ciphertext)
+        .map_err(|_| {
+            anyhow::anyhow!("Failed to decrypt private key: wrong passphrase or corrupted file")
+        })?;
+
+    if plaintext.len() != SECRET_KEY_LENGTH {
+        anyhow::bail!(
+            "Decrypted key has unexpected length: expected {}, got {}",
+            SECRET_KEY_LENGTH,
+            plaintext.len()
+        );
+    }
+
+    let mut key_bytes = [0u8; SECRET_KEY_LENGTH];
+    key_bytes.copy_from_slice(&plaintext);
+    Ok(key_bytes)
+}
+
+/// Derive a 32-byte wrapping key from `passphrase` with Argon2id
+fn derive_wrapping_key(
+    passphrase: &str,
+    salt: &[u8],
+    m_cost: u32,
+    t_cost: u32,
+    p_cost: u32,
+) -> Result<[u8; 32]> {
+    let params = Params::new(m_cost, t_cost, p_cost, Some(32))
+        .map_err(|e| anyhow::anyhow!("Invalid Argon2 parameters: {}", e))?;
+    let argon2 = Argon2::new(Algorithm::Argon2id, Version::V0x13, params);
+
+    let mut wrapping_key = [0u8; 32];
+    argon2
+        .hash_password_into(passphrase.as_bytes(), salt, &mut wrapping_key)
+        .map_err(|e| anyhow::anyhow!("Argon2id key derivation failed: {}", e))?;
+
+    Ok(wrapping_key)
+}
+
+/// Label used for armored public key blocks
+const PUBLIC_KEY_LABEL: &str = "PUBLIC KEY";
+/// Label used for armored private key containers (plaintext or sealed)
+const PRIVATE_KEY_LABEL: &str = "PRIVATE KEY";
+
+/// Parse a public key from its text representation, auto-detecting
+/// ASCII-armored vs. legacy hex encoding the same way a key file on disk
+/// might use either
+pub fn parse_public_key(text: &str) -> Result<VerifyingKey> {
+    let bytes = if armor::looks_armored(text) {
+        armor::decode(PUBLIC_KEY_LABEL, text)?
+    } else {
+        hex::decode(text.trim()).context("Invalid hex encoding")?
+    };
 
     if bytes.len() != PUBLIC_KEY_LENGTH {
         anyhow::bail!(
@@ -92,6 +322,18 @@ pub fn load_public_key(path: impl AsRef<Path>) -> Result<VerifyingKey> {
     Ok(VerifyingKey::from_bytes(&key_bytes)?)
 }
 
+/// Load public key from file (hex-encoded, or ASCII-armored)
+pub fn load_public_key(path: impl AsRef<Path>) -> Result<VerifyingKey> {
+    let text = fs::read_to_string(path).context("Failed to read public key file")?;
+    parse_public_key(&text)
+}
+
+/// Export the public key as an ASCII-armored block, for sharing through
+/// email or chat rather than shipping a raw hex file
+pub fn export_public_key_armored(key: &VerifyingKey) -> String {
+    armor::encode(PUBLIC_KEY_LABEL, &key.to_bytes())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -145,4 +387,90 @@ mod tests {
         let result = KeyPair::load_private(&path);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_keygen_save_load_encrypted() {
+        let temp_dir = TempDir::new().unwrap();
+        let private_path = temp_dir.path().join("private.key.enc");
+        let public_path = temp_dir.path().join("public.key");
+
+        let original = KeyPair::generate();
+        original
+            .save_encrypted(&private_path, &public_path, "correct horse battery staple")
+            .unwrap();
+
+        let loaded =
+            KeyPair::load_private_encrypted(&private_path, "correct horse battery staple").unwrap();
+        assert_eq!(
+            original.verifying_key().to_bytes(),
+            loaded.verifying_key().to_bytes()
+        );
+    }
+
+    #[test]
+    fn test_load_private_encrypted_wrong_passphrase() {
+        let temp_dir = TempDir::new().unwrap();
+        let private_path = temp_dir.path().join("private.key.enc");
+        let public_path = temp_dir.path().join("public.key");
+
+        KeyPair::generate()
+            .save_encrypted(&private_path, &public_path, "right passphrase")
+            .unwrap();
+
+        let result = KeyPair::load_private_encrypted(&private_path, "wrong passphrase");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_load_private_encrypted_falls_back_to_legacy_plaintext() {
+        let temp_dir = TempDir::new().unwrap();
+        let private_path = temp_dir.path().join("private.key");
+        let public_path = temp_dir.path().join("public.key");
+
+        let original = KeyPair::generate();
+        original.save(&private_path, &public_path).unwrap();
+
+        // Passphrase is ignored for legacy plaintext keys, detected via magic bytes.
+        let loaded = KeyPair::load_private_encrypted(&private_path, "unused").unwrap();
+        assert_eq!(
+            original.verifying_key().to_bytes(),
+            loaded.verifying_key().to_bytes()
+        );
+    }
+
+    #[test]
+    fn test_public_key_armored_round_trip() {
+        let original = KeyPair::generate();
+        let armored = original.export_public_armored();
+        assert!(armor::looks_armored(&armored));
+
+        let loaded = import_public_key_armored(&armored).unwrap();
+        assert_eq!(original.verifying_key().to_bytes(), loaded.to_bytes());
+
+        // parse_public_key should auto-detect the armored form too
+        let auto_detected = parse_public_key(&armored).unwrap();
+        assert_eq!(original.verifying_key().to_bytes(), auto_detected.to_bytes());
+    }
+
+    #[test]
+    fn test_private_key_armored_round_trip() {
+        let original = KeyPair::generate();
+        let armored = original.export_private_armored("correct horse battery staple").unwrap();
+
+        let loaded =
+            KeyPair::import_private_armored(&armored, "correct horse battery staple").unwrap();
+        assert_eq!(
+            original.verifying_key().to_bytes(),
+            loaded.verifying_key().to_bytes()
+        );
+    }
+
+    #[test]
+    fn test_private_key_armored_wrong_passphrase() {
+        let original = KeyPair::generate();
+        let armored = original.export_private_armored("right passphrase").unwrap();
+
+        let result = KeyPair::import_private_armored(&armored, "wrong passphrase");
+        assert!(result.is_err());
+    }
 }
@@ -0,0 +1,10 @@
+//! Cryptographic primitives: Ed25519 signing keys, password-based archive
+//! encryption, ASCII armor for sharing key material as text, public key
+//! resolution for verifying signatures from a keyserver or local directory,
+//! and X25519 keypairs for layered-archive recipient key agreement.
+
+pub mod armor;
+pub mod keys;
+pub mod password;
+pub mod resolver;
+pub mod x25519;
@@ -0,0 +1,98 @@
+//! Password acquisition for archive encryption and decryption
+//!
+//! Mirrors how mature archivers source a password for `--encrypt`/
+//! `--decrypt`: an explicit `--password-file`, the `STRATEGOS_PASSWORD`
+//! environment variable, or — only as a last resort — an interactive
+//! terminal prompt with echo disabled. Precedence is file > env > prompt,
+//! so a script can always override the interactive default without
+//! editing anything. [`resolve`] is for operations that always need a
+//! password; [`resolve_optional`] is for `list`/`info`, which only need
+//! one when the archive turns out to be encrypted, and so never fall back
+//! to a prompt on their own.
+
+use anyhow::{Context, Result};
+use std::path::Path;
+
+/// Environment variable checked after `--password-file` and before an
+/// interactive prompt
+pub const PASSWORD_ENV_VAR: &str = "STRATEGOS_PASSWORD";
+
+/// Resolve a password for an operation that always needs one (`pack
+/// --encrypt`, `extract --decrypt`): try `password_file`, then
+/// [`PASSWORD_ENV_VAR`], then prompt interactively with echo disabled.
+///
+/// When `confirm` is set (packing, where a typo would lock the user out of
+/// their own archive) the prompt is asked twice and the two entries must
+/// match; `extract --decrypt` doesn't confirm since there's nothing to
+/// protect against re-typing a password that's already fixed.
+pub fn resolve(password_file: Option<&Path>, confirm: bool) -> Result<String> {
+    if let Some(path) = password_file {
+        return read_password_file(path);
+    }
+
+    if let Ok(password) = std::env::var(PASSWORD_ENV_VAR) {
+        return Ok(password);
+    }
+
+    let password = rpassword::prompt_password("Password: ").context("Failed to read password")?;
+    if confirm {
+        let confirmation = rpassword::prompt_password("Confirm password: ")
+            .context("Failed to read password confirmation")?;
+        if password != confirmation {
+            anyhow::bail!("Passwords do not match");
+        }
+    }
+
+    Ok(password)
+}
+
+/// Resolve a password for `list`/`info`, which should only ever need one
+/// for an archive that turns out to be encrypted. Tries `password_file`
+/// and [`PASSWORD_ENV_VAR`] the same as [`resolve`], but never falls back
+/// to an interactive prompt — inspecting an archive shouldn't stop to ask
+/// for a password it may not even need.
+pub fn resolve_optional(password_file: Option<&Path>) -> Result<Option<String>> {
+    if let Some(path) = password_file {
+        return Ok(Some(read_password_file(path)?));
+    }
+
+    Ok(std::env::var(PASSWORD_ENV_VAR).ok())
+}
+
+/// Read a password file, trimming the single trailing newline an editor or
+/// `echo` would leave behind
+fn read_password_file(path: &Path) -> Result<String> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read password file: {}", path.display()))?;
+    Ok(contents.trim_end_matches(['\n', '\r']).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_resolve_optional_reads_password_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("password.txt");
+        std::fs::write(&path, "hunter2\n").unwrap();
+
+        assert_eq!(resolve_optional(Some(&path)).unwrap(), Some("hunter2".to_string()));
+    }
+
+    #[test]
+    fn test_resolve_optional_returns_none_without_file_or_env() {
+        std::env::remove_var(PASSWORD_ENV_VAR);
+        assert_eq!(resolve_optional(None).unwrap(), None);
+    }
+
+    #[test]
+    fn test_read_password_file_trims_trailing_newline() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("password.txt");
+        std::fs::write(&path, "correct horse battery staple\r\n").unwrap();
+
+        assert_eq!(read_password_file(&path).unwrap(), "correct horse battery staple");
+    }
+}
@@ -0,0 +1,104 @@
+//! Key resolution for verifying signatures from third-party signers
+//!
+//! A manifest signature only proves the bytes were signed by whatever key
+//! is embedded alongside it; it says nothing about whether *that* key
+//! should be trusted. `KeyResolver` looks up the verifying key a signer's
+//! key-id is supposed to map to — from a local trust directory or a remote
+//! keyserver — so callers can compare it against the key embedded in the
+//! manifest before deciding to trust a third-party archive.
+
+use anyhow::{Context, Result};
+use ed25519_dalek::VerifyingKey;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use super::{armor, keys};
+
+/// A request to resolve a manifest signer's key-id against a trust source
+pub trait KeyResolver {
+    /// Resolve `key_id` to the verifying key it's supposed to map to
+    fn resolve(&self, key_id: &str) -> Result<ResolvedKey>;
+}
+
+/// A key resolved from some external source, plus where it came from, so
+/// callers can report provenance alongside the verification result
+#[derive(Debug, Clone)]
+pub struct ResolvedKey {
+    pub key: VerifyingKey,
+    pub source: String,
+}
+
+/// Resolves key-ids against `<dir>/<key_id>.pub`, accepting either the
+/// legacy hex format or an ASCII-armored block (auto-detected, same as
+/// [`keys::load_public_key`]).
+pub struct LocalDirectoryResolver {
+    pub dir: PathBuf,
+}
+
+impl LocalDirectoryResolver {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+}
+
+impl KeyResolver for LocalDirectoryResolver {
+    fn resolve(&self, key_id: &str) -> Result<ResolvedKey> {
+        let path = self.dir.join(format!("{}.pub", key_id));
+        let key = keys::load_public_key(&path)
+            .with_context(|| format!("Failed to resolve key '{}' from {}", key_id, self.dir.display()))?;
+
+        Ok(ResolvedKey {
+            key,
+            source: format!("local:{}", path.display()),
+        })
+    }
+}
+
+/// Resolves key-ids by fetching `<base_url>/<key_id>` from an HTTP
+/// keyserver. The response body is expected to be hex or ASCII-armored,
+/// same as a key file on disk.
+pub struct HttpKeyserverResolver {
+    pub base_url: String,
+    pub timeout: Duration,
+}
+
+impl HttpKeyserverResolver {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+impl KeyResolver for HttpKeyserverResolver {
+    fn resolve(&self, key_id: &str) -> Result<ResolvedKey> {
+        let url = format!("{}/{}", self.base_url.trim_end_matches('/'), key_id);
+
+        let body: String = ureq::get(&url)
+            .timeout(self.timeout)
+            .call()
+            .with_context(|| format!("Failed to fetch key '{}' from keyserver {}", key_id, url))?
+            .into_string()
+            .with_context(|| format!("Keyserver response for '{}' was not valid text", key_id))?;
+
+        let key = keys::parse_public_key(&body)
+            .with_context(|| format!("Keyserver returned an invalid key for '{}'", key_id))?;
+
+        Ok(ResolvedKey {
+            key,
+            source: format!("keyserver:{}", url),
+        })
+    }
+}
+
+/// Whether `candidate` byte-for-byte matches the key embedded in a
+/// signature, for flagging a third-party archive whose embedded key
+/// doesn't match what the configured resolver says it should be
+pub fn keys_match(candidate: &VerifyingKey, embedded: &VerifyingKey) -> bool {
+    candidate.to_bytes() == embedded.to_bytes()
+}
+
+// Re-exported so resolver implementations can detect armored vs. hex
+// bodies the same way key files on disk do.
+pub use armor::looks_armored;
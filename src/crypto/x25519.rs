@@ -0,0 +1,83 @@
+//! X25519 keypairs for layered-archive recipient key agreement
+//!
+//! Distinct from [`super::keys`]'s Ed25519 signing keys: this is agreement,
+//! not signing. `crate::formats::layered`'s encryption layer Diffie-Hellman's
+//! a fresh ephemeral key against a recipient's long-term public key here to
+//! derive a one-off AES-256 key, the classic ECIES shape.
+
+use anyhow::{Context, Result};
+use rand::rngs::OsRng;
+use std::fs;
+use std::path::Path;
+use x25519_dalek::{PublicKey, StaticSecret};
+
+const KEY_LEN: usize = 32;
+
+/// X25519 keypair for a layered-archive recipient
+pub struct X25519KeyPair {
+    secret: StaticSecret,
+}
+
+impl X25519KeyPair {
+    /// Generate a new random keypair
+    pub fn generate() -> Self {
+        Self {
+            secret: StaticSecret::random_from_rng(OsRng),
+        }
+    }
+
+    /// Create a keypair from raw private key bytes
+    pub fn from_bytes(bytes: [u8; KEY_LEN]) -> Self {
+        Self {
+            secret: StaticSecret::from(bytes),
+        }
+    }
+
+    /// Load a keypair from a private key file (hex-encoded)
+    pub fn load_private(path: impl AsRef<Path>) -> Result<Self> {
+        let hex_str = fs::read_to_string(path).context("Failed to read private key file")?;
+        let bytes = hex::decode(hex_str.trim()).context("Invalid hex encoding")?;
+
+        if bytes.len() != KEY_LEN {
+            anyhow::bail!("Invalid key length: expected {}, got {}", KEY_LEN, bytes.len());
+        }
+
+        let mut key_bytes = [0u8; KEY_LEN];
+        key_bytes.copy_from_slice(&bytes);
+        Ok(Self::from_bytes(key_bytes))
+    }
+
+    /// The static private key, used to peel an encryption layer
+    pub fn secret(&self) -> &StaticSecret {
+        &self.secret
+    }
+
+    /// The public key, shared with senders so they can encrypt to this recipient
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey::from(&self.secret)
+    }
+
+    /// Save keypair to files (hex-encoded)
+    pub fn save(&self, private_path: impl AsRef<Path>, public_path: impl AsRef<Path>) -> Result<()> {
+        fs::write(private_path, hex::encode(self.secret.to_bytes()))
+            .context("Failed to write private key")?;
+        fs::write(public_path, hex::encode(self.public_key().to_bytes()))
+            .context("Failed to write public key")?;
+        Ok(())
+    }
+}
+
+/// Load a recipient's public key from a hex-encoded file, for senders that
+/// only need to encrypt to a recipient, not decrypt as one.
+pub fn load_public_key(path: impl AsRef<Path>) -> Result<PublicKey> {
+    let hex_str = fs::read_to_string(path).context("Failed to read public key file")?;
+    let bytes = hex::decode(hex_str.trim()).context("Invalid hex encoding")?;
+
+    if bytes.len() != KEY_LEN {
+        anyhow::bail!("Invalid public key length: expected {}, got {}", KEY_LEN, bytes.len());
+    }
+
+    let mut key_bytes = [0u8; KEY_LEN];
+    key_bytes.copy_from_slice(&bytes);
+    Ok(PublicKey::from(key_bytes))
+}
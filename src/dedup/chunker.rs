@@ -0,0 +1,158 @@
+//! Content-defined chunking via a rolling buzhash
+//!
+//! Boundaries are cut wherever the low bits of a rolling hash over a sliding
+//! window are zero, so an insertion or deletion only re-chunks the bytes
+//! local to the edit rather than the whole file.
+
+const WINDOW: usize = 64;
+
+/// Min/avg/max chunk size bounds, in bytes.
+#[derive(Debug, Clone, Copy)]
+pub struct ChunkerConfig {
+    pub min_size: usize,
+    pub avg_size: usize,
+    pub max_size: usize,
+}
+
+impl Default for ChunkerConfig {
+    fn default() -> Self {
+        Self {
+            min_size: 2 * 1024,
+            avg_size: 8 * 1024,
+            max_size: 64 * 1024,
+        }
+    }
+}
+
+impl ChunkerConfig {
+    /// Number of low bits that must be zero to cut a boundary, derived from
+    /// the target average chunk size (a power of two).
+    fn mask_bits(&self) -> u32 {
+        (self.avg_size.max(1) as u32).trailing_zeros()
+    }
+}
+
+/// Precomputed per-byte random values for the buzhash, generated once from a
+/// fixed seed so chunking is deterministic across runs.
+fn gear_table() -> &'static [u64; 256] {
+    static TABLE: std::sync::OnceLock<[u64; 256]> = std::sync::OnceLock::new();
+    TABLE.get_or_init(|| {
+        let mut table = [0u64; 256];
+        let mut state: u64 = 0x9E3779B97F4A7C15;
+        for slot in table.iter_mut() {
+            // splitmix64
+            state = state.wrapping_add(0x9E3779B97F4A7C15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+            *slot = z ^ (z >> 31);
+        }
+        table
+    })
+}
+
+/// Split `data` into content-defined chunks according to `config`.
+///
+/// Returns byte ranges (start, end) into `data`; the final chunk always ends
+/// at `data.len()`.
+pub fn chunk_boundaries(data: &[u8], config: ChunkerConfig) -> Vec<(usize, usize)> {
+    if data.is_empty() {
+        return Vec::new();
+    }
+
+    let table = gear_table();
+    let mask = (1u64 << config.mask_bits()) - 1;
+
+    let mut boundaries = Vec::new();
+    let mut start = 0usize;
+    let mut hash: u64 = 0;
+
+    for (i, &byte) in data.iter().enumerate() {
+        hash = hash.rotate_left(1) ^ table[byte as usize];
+
+        let len = i + 1 - start;
+        if len < config.min_size {
+            continue;
+        }
+
+        let window_full = len >= WINDOW;
+        let hit_boundary = window_full && (hash & mask) == 0;
+
+        if hit_boundary || len >= config.max_size {
+            boundaries.push((start, i + 1));
+            start = i + 1;
+            hash = 0;
+        }
+    }
+
+    if start < data.len() {
+        boundaries.push((start, data.len()));
+    }
+
+    boundaries
+}
+
+/// Split `data` into owned chunk byte slices.
+pub fn chunk(data: &[u8], config: ChunkerConfig) -> Vec<&[u8]> {
+    chunk_boundaries(data, config)
+        .into_iter()
+        .map(|(start, end)| &data[start..end])
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_input() {
+        assert!(chunk_boundaries(&[], ChunkerConfig::default()).is_empty());
+    }
+
+    #[test]
+    fn test_boundaries_cover_whole_input() {
+        let data: Vec<u8> = (0..100_000u32).map(|i| (i % 251) as u8).collect();
+        let boundaries = chunk_boundaries(&data, ChunkerConfig::default());
+
+        assert_eq!(boundaries.first().unwrap().0, 0);
+        assert_eq!(boundaries.last().unwrap().1, data.len());
+
+        for window in boundaries.windows(2) {
+            assert_eq!(window[0].1, window[1].0);
+        }
+    }
+
+    #[test]
+    fn test_respects_max_size() {
+        let data = vec![0u8; 1_000_000]; // constant bytes never hit a hash boundary
+        let config = ChunkerConfig {
+            min_size: 1024,
+            avg_size: 8192,
+            max_size: 16 * 1024,
+        };
+        let boundaries = chunk_boundaries(&data, config);
+
+        for (start, end) in boundaries {
+            assert!(end - start <= config.max_size);
+        }
+    }
+
+    #[test]
+    fn test_local_edit_reuses_most_chunks() {
+        let base: Vec<u8> = (0..200_000u32).map(|i| (i % 233) as u8).collect();
+        let mut edited = base.clone();
+        edited.insert(100_000, 7);
+
+        let config = ChunkerConfig::default();
+        let base_chunks: std::collections::HashSet<&[u8]> =
+            chunk(&base, config).into_iter().collect();
+        let edited_chunks = chunk(&edited, config);
+
+        let reused = edited_chunks
+            .iter()
+            .filter(|c| base_chunks.contains(*c))
+            .count();
+
+        assert!(reused as f64 / edited_chunks.len() as f64 > 0.5);
+    }
+}
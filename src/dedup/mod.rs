@@ -0,0 +1,360 @@
+//! Content-defined deduplication store
+//!
+//! Chunks file contents with a rolling hash and keys each unique chunk by its
+//! BLAKE3 digest, so archives with repeated content can report (and, for
+//! formats that implement [`DedupArchive`], physically store) only the
+//! unique bytes.
+
+pub mod chunker;
+
+use anyhow::{Context, Result};
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+
+use crate::formats::traits::OutputFormat;
+use crate::formats::Archive;
+use chunker::ChunkerConfig;
+
+/// Metadata for a single unique chunk.
+#[derive(Debug, Clone)]
+pub struct ChunkMeta {
+    pub size: usize,
+    /// Number of files referencing this chunk.
+    pub ref_count: usize,
+}
+
+/// A file's reconstruction recipe: the ordered list of chunk digests whose
+/// concatenation is the file's contents.
+pub type Recipe = Vec<[u8; 32]>;
+
+/// In-memory index of unique chunks, keyed by BLAKE3 digest.
+///
+/// Beyond the read-only stats tracked by [`ingest`](ChunkStore::ingest), a
+/// store can also hold the chunk bytes themselves so a [`DedupArchive`] can
+/// physically write only unseen chunks and reassemble a file later from its
+/// [`Recipe`].
+#[derive(Default)]
+pub struct ChunkStore {
+    chunks: HashMap<[u8; 32], ChunkMeta>,
+    bytes: HashMap<[u8; 32], Vec<u8>>,
+    /// Digests already present in the backing archive/store, merged in via
+    /// [`merge_known`](ChunkStore::merge_known) without their bytes.
+    known: HashSet<[u8; 32]>,
+}
+
+impl ChunkStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Chunk `data` and record each chunk's digest, returning the ordered
+    /// list of digests that reconstructs `data`.
+    pub fn ingest(&mut self, data: &[u8], config: ChunkerConfig) -> Vec<[u8; 32]> {
+        let mut digests = Vec::new();
+
+        for piece in chunker::chunk(data, config) {
+            let digest: [u8; 32] = blake3::hash(piece).into();
+
+            self.chunks
+                .entry(digest)
+                .and_modify(|meta| meta.ref_count += 1)
+                .or_insert(ChunkMeta {
+                    size: piece.len(),
+                    ref_count: 1,
+                });
+
+            digests.push(digest);
+        }
+
+        digests
+    }
+
+    /// Chunk `data`, physically storing the bytes of any chunk not already
+    /// known to this store (whether ingested earlier or merged in via
+    /// [`merge_known`](ChunkStore::merge_known)).
+    ///
+    /// Returns the file's reconstruction recipe alongside the digests that
+    /// were newly stored, i.e. the chunks a caller actually needs to persist
+    /// to the target archive.
+    pub fn ingest_for_storage(
+        &mut self,
+        data: &[u8],
+        config: ChunkerConfig,
+    ) -> (Recipe, Vec<[u8; 32]>) {
+        let mut recipe = Vec::new();
+        let mut newly_stored = Vec::new();
+
+        for piece in chunker::chunk(data, config) {
+            let digest: [u8; 32] = blake3::hash(piece).into();
+            recipe.push(digest);
+
+            if self.known.contains(&digest) {
+                if let Some(meta) = self.chunks.get_mut(&digest) {
+                    meta.ref_count += 1;
+                }
+                continue;
+            }
+
+            match self.chunks.entry(digest) {
+                Entry::Occupied(mut e) => e.get_mut().ref_count += 1,
+                Entry::Vacant(e) => {
+                    e.insert(ChunkMeta {
+                        size: piece.len(),
+                        ref_count: 1,
+                    });
+                    self.bytes.insert(digest, piece.to_vec());
+                    newly_stored.push(digest);
+                }
+            }
+        }
+
+        (recipe, newly_stored)
+    }
+
+    /// Merge in digests already present in the archive (or shared store)
+    /// being written to, so a later [`ingest_for_storage`](ChunkStore::ingest_for_storage)
+    /// call recognizes them as known and re-archiving a mostly-unchanged
+    /// dataset only stores the chunks that actually changed.
+    ///
+    /// Unlike `ingest`, this takes bare digests with no bytes, since the
+    /// whole point is to avoid re-reading data the target archive already
+    /// has.
+    pub fn merge_known(&mut self, digests: impl IntoIterator<Item = [u8; 32]>) {
+        self.known.extend(digests);
+    }
+
+    /// Look up a previously stored chunk's bytes by digest.
+    pub fn get_chunk(&self, digest: &[u8; 32]) -> Option<&[u8]> {
+        self.bytes.get(digest).map(Vec::as_slice)
+    }
+
+    /// Reassemble a file's contents from its reconstruction recipe, in order.
+    pub fn reassemble(&self, recipe: &[[u8; 32]]) -> Result<Vec<u8>> {
+        let mut out = Vec::with_capacity(recipe.len());
+
+        for digest in recipe {
+            let chunk = self
+                .get_chunk(digest)
+                .with_context(|| format!("Missing chunk {} referenced by recipe", hex::encode(digest)))?;
+            out.extend_from_slice(chunk);
+        }
+
+        Ok(out)
+    }
+
+    pub fn unique_chunk_count(&self) -> usize {
+        self.chunks.len()
+    }
+
+    pub fn unique_bytes(&self) -> u64 {
+        self.chunks.values().map(|m| m.size as u64).sum()
+    }
+}
+
+/// Trait for formats that can physically store chunk-referenced data rather
+/// than whole-file copies (e.g. a writable DataSpool or Cartridge).
+pub trait DedupArchive {
+    /// Write `path` as a list of chunk references instead of a verbatim copy,
+    /// deduplicating against chunks already known to `store`.
+    ///
+    /// Implementations should call [`ChunkStore::ingest_for_storage`] to get
+    /// the file's [`Recipe`] and the digests that are actually new, persist
+    /// only those chunk bytes, and record the recipe as the means to
+    /// reconstruct `path` on read.
+    fn write_file_deduped(
+        &mut self,
+        path: &str,
+        data: &[u8],
+        store: &mut ChunkStore,
+        config: ChunkerConfig,
+    ) -> Result<()>;
+
+    /// Read back a file previously written with `write_file_deduped`,
+    /// reassembling its chunks in order via [`ChunkStore::reassemble`].
+    fn read_file_deduped(&mut self, path: &str, store: &ChunkStore) -> Result<Vec<u8>>;
+}
+
+/// Per-file dedup breakdown for the `dups` report.
+pub struct FileDedupStats {
+    pub path: String,
+    pub total_bytes: u64,
+    pub unique_bytes: u64,
+    pub shared_bytes: u64,
+}
+
+/// Overall dedup statistics across an archive's file list.
+pub struct DedupReport {
+    pub files: Vec<FileDedupStats>,
+    pub total_bytes: u64,
+    pub unique_bytes: u64,
+}
+
+impl DedupReport {
+    pub fn ratio(&self) -> f64 {
+        if self.unique_bytes == 0 {
+            1.0
+        } else {
+            self.total_bytes as f64 / self.unique_bytes as f64
+        }
+    }
+}
+
+/// Walk `archive.list_files()`, chunk every entry, and report dedup stats.
+pub fn analyze(archive: &mut dyn Archive, config: ChunkerConfig) -> Result<DedupReport> {
+    let files = archive.list_files()?;
+    let mut store = ChunkStore::new();
+
+    // First pass: ingest every file's chunks into a shared store so repeats
+    // across files are recognized.
+    let mut per_file_digests = Vec::with_capacity(files.len());
+    for entry in &files {
+        let data = archive.read_file(&entry.path)?;
+        let digests = store.ingest(&data, config);
+        per_file_digests.push((entry.path.clone(), data.len() as u64, digests));
+    }
+
+    // Second pass: now that ref counts are final, attribute each file's bytes
+    // to "unique to this file" vs. "shared with something else".
+    let mut file_stats = Vec::with_capacity(per_file_digests.len());
+    let mut total_bytes = 0u64;
+
+    for (path, total, digests) in per_file_digests {
+        let mut shared = 0u64;
+        for digest in &digests {
+            if let Some(meta) = store.chunks.get(digest) {
+                if meta.ref_count > 1 {
+                    shared += meta.size as u64;
+                }
+            }
+        }
+
+        total_bytes += total;
+        file_stats.push(FileDedupStats {
+            path,
+            total_bytes: total,
+            unique_bytes: total.saturating_sub(shared),
+            shared_bytes: shared,
+        });
+    }
+
+    Ok(DedupReport {
+        files: file_stats,
+        total_bytes,
+        unique_bytes: store.unique_bytes(),
+    })
+}
+
+/// Render a [`DedupReport`] in the requested [`OutputFormat`].
+pub fn format_report(report: &DedupReport, format: OutputFormat) -> Result<String> {
+    match format {
+        OutputFormat::Json => {
+            let files: Vec<serde_json::Value> = report
+                .files
+                .iter()
+                .map(|f| {
+                    serde_json::json!({
+                        "path": f.path,
+                        "total_bytes": f.total_bytes,
+                        "unique_bytes": f.unique_bytes,
+                        "shared_bytes": f.shared_bytes,
+                    })
+                })
+                .collect();
+
+            let value = serde_json::json!({
+                "total_bytes": report.total_bytes,
+                "unique_bytes": report.unique_bytes,
+                "dedup_ratio": report.ratio(),
+                "files": files,
+            });
+
+            Ok(serde_json::to_string_pretty(&value)?)
+        }
+        OutputFormat::Csv => {
+            let mut out = String::from("path,total_bytes,unique_bytes,shared_bytes\n");
+            for f in &report.files {
+                out.push_str(&format!(
+                    "{},{},{},{}\n",
+                    f.path, f.total_bytes, f.unique_bytes, f.shared_bytes
+                ));
+            }
+            Ok(out)
+        }
+        OutputFormat::Table => {
+            let mut out = format!(
+                "{:<50} {:>12} {:>12} {:>12}\n",
+                "PATH", "TOTAL", "UNIQUE", "SHARED"
+            );
+            for f in &report.files {
+                out.push_str(&format!(
+                    "{:<50} {:>12} {:>12} {:>12}\n",
+                    f.path, f.total_bytes, f.unique_bytes, f.shared_bytes
+                ));
+            }
+            out.push_str(&format!(
+                "\nTotal: {} bytes, unique: {} bytes, dedup ratio: {:.2}x\n",
+                report.total_bytes,
+                report.unique_bytes,
+                report.ratio()
+            ));
+            Ok(out)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ingest_for_storage_reports_only_new_chunks() {
+        let mut store = ChunkStore::new();
+        let config = ChunkerConfig::default();
+        let data: Vec<u8> = (0..50_000u32).map(|i| (i % 211) as u8).collect();
+
+        let (recipe_a, new_a) = store.ingest_for_storage(&data, config);
+        assert_eq!(new_a.len(), recipe_a.len());
+
+        let (recipe_b, new_b) = store.ingest_for_storage(&data, config);
+        assert_eq!(recipe_b, recipe_a);
+        assert!(new_b.is_empty(), "re-ingesting identical data should store nothing new");
+    }
+
+    #[test]
+    fn test_reassemble_round_trips() {
+        let mut store = ChunkStore::new();
+        let config = ChunkerConfig::default();
+        let data: Vec<u8> = (0..50_000u32).map(|i| (i % 197) as u8).collect();
+
+        let (recipe, _) = store.ingest_for_storage(&data, config);
+        let reassembled = store.reassemble(&recipe).unwrap();
+
+        assert_eq!(reassembled, data);
+    }
+
+    #[test]
+    fn test_merge_known_skips_re_storing() {
+        let mut source = ChunkStore::new();
+        let config = ChunkerConfig::default();
+        let data: Vec<u8> = (0..50_000u32).map(|i| (i % 181) as u8).collect();
+        let (recipe, _) = source.ingest_for_storage(&data, config);
+
+        // A fresh store standing in for re-archiving the same dataset: seed
+        // it with the digests the target archive already has, then ingest
+        // the (mostly unchanged) data again.
+        let mut target = ChunkStore::new();
+        target.merge_known(recipe.iter().copied());
+        let (_, newly_stored) = target.ingest_for_storage(&data, config);
+
+        assert!(newly_stored.is_empty());
+        assert_eq!(target.unique_chunk_count(), 0);
+    }
+
+    #[test]
+    fn test_reassemble_missing_chunk_errors() {
+        let store = ChunkStore::new();
+        let bogus_recipe = vec![[0u8; 32]];
+
+        assert!(store.reassemble(&bogus_recipe).is_err());
+    }
+}
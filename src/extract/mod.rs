@@ -0,0 +1,492 @@
+//! Shared extraction safety checks
+//!
+//! Every format's `extract` used to join an archive-supplied path straight
+//! onto the output directory and write whatever the archive said to, with
+//! no limit on how much it would write. `ExtractGuard` centralizes the
+//! checks untrusted snapshot tarballs are normally unpacked under: reject
+//! path-traversal components before ever touching disk, canonicalize the
+//! output root once and verify every resolved target still falls under it,
+//! and track running uncompressed-byte, on-disk-byte, and file-count totals
+//! against configurable ceilings so a zip-bomb-style archive is caught
+//! before it's fully written rather than after. Every format's `extract`
+//! routes entries through this, and `validate_path_components` alone (no
+//! filesystem access) is shared with `list`/`info --inspect` so they can
+//! flag the same unsafe entries without writing anything.
+
+use anyhow::{Context, Result};
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Resource ceilings enforced while unpacking an archive
+#[derive(Debug, Clone, Copy)]
+pub struct ExtractLimits {
+    pub max_unpacked_size: u64,
+    pub max_files: u64,
+}
+
+impl Default for ExtractLimits {
+    fn default() -> Self {
+        Self {
+            max_unpacked_size: 4 * 1024 * 1024 * 1024, // 4 GiB
+            max_files: 5_000_000,
+        }
+    }
+}
+
+/// Check `entry_path`'s components without touching the filesystem,
+/// rejecting anything that could walk a joined path outside its root, or
+/// that's not a plain relative segment: a `..` (`ParentDir`), an absolute
+/// root, a Windows prefix, or a `.` (`CurDir`) marker. Only `Normal`
+/// components are accepted.
+pub fn validate_path_components(entry_path: &str) -> Result<()> {
+    for component in Path::new(entry_path).components() {
+        match component {
+            Component::Normal(_) => {}
+            Component::CurDir => {
+                anyhow::bail!(
+                    "Unsafe entry '{}': contains a current-directory ('.') component",
+                    entry_path
+                );
+            }
+            Component::ParentDir => {
+                anyhow::bail!(
+                    "Unsafe entry '{}': contains a parent-directory ('..') component",
+                    entry_path
+                );
+            }
+            Component::RootDir | Component::Prefix(_) => {
+                anyhow::bail!("Unsafe entry '{}': absolute paths are not allowed", entry_path);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Validates entry paths and tracks cumulative size/file count against
+/// `limits` while an archive is unpacked
+pub struct ExtractGuard {
+    output_root: PathBuf,
+    limits: ExtractLimits,
+    total_claimed_bytes: u64,
+    total_written_bytes: u64,
+    total_files: u64,
+}
+
+impl ExtractGuard {
+    /// Create a guard rooted at `output`, canonicalizing it once up front
+    /// (creating it first, since canonicalization requires the path to
+    /// already exist)
+    pub fn new(output: &Path, limits: ExtractLimits) -> Result<Self> {
+        std::fs::create_dir_all(output)
+            .with_context(|| format!("Failed to create output directory: {}", output.display()))?;
+        let output_root = output
+            .canonicalize()
+            .with_context(|| format!("Failed to resolve output directory: {}", output.display()))?;
+
+        Ok(Self {
+            output_root,
+            limits,
+            total_claimed_bytes: 0,
+            total_written_bytes: 0,
+            total_files: 0,
+        })
+    }
+
+    /// Validate `entry_path` against traversal and resolve it to a path
+    /// under the output root, creating any intermediate directories needed
+    /// to canonicalize it
+    ///
+    /// Canonicalizing the parent (rather than the never-yet-existing target
+    /// itself) and re-checking it against `output_root` catches an escape
+    /// that only resolves once joined, e.g. via a symlinked intermediate
+    /// directory, not just a literal `..` in the entry path.
+    pub fn resolve(&self, entry_path: &str) -> Result<PathBuf> {
+        resolve_under(&self.output_root, entry_path)
+    }
+
+    /// Verify that a symlink about to be created at `link_path` (itself
+    /// already resolved under the output root by [`ExtractGuard::resolve`])
+    /// wouldn't point somewhere outside the output root once followed
+    pub fn validate_symlink_target(&self, link_path: &Path, target: &str) -> Result<()> {
+        validate_symlink_target_under(&self.output_root, link_path, target)
+    }
+
+    /// Account for one entry's claimed (uncompressed) size and file count,
+    /// erroring out before anything is written if either running total
+    /// would exceed its configured ceiling, or if the running byte total
+    /// itself overflows (an archive lying about a field that large is
+    /// itself a reason to refuse it)
+    pub fn charge(&mut self, entry_size: u64) -> Result<()> {
+        self.total_files += 1;
+        if self.total_files > self.limits.max_files {
+            anyhow::bail!(
+                "Refusing to extract: archive contains more than {} files",
+                self.limits.max_files
+            );
+        }
+
+        self.total_claimed_bytes = self
+            .total_claimed_bytes
+            .checked_add(entry_size)
+            .context("Refusing to extract: uncompressed size overflowed")?;
+        if self.total_claimed_bytes > self.limits.max_unpacked_size {
+            anyhow::bail!(
+                "Refusing to extract: uncompressed size would exceed {} bytes",
+                self.limits.max_unpacked_size
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Account for bytes actually flushed to disk for the entry most
+    /// recently passed to [`ExtractGuard::charge`], tracked separately from
+    /// the claimed size so a format that streams writes incrementally in
+    /// the future can't outrun the limit `charge` already checked up front
+    pub fn charge_written(&mut self, written_size: u64) -> Result<()> {
+        self.total_written_bytes = self
+            .total_written_bytes
+            .checked_add(written_size)
+            .context("Refusing to extract: on-disk size overflowed")?;
+        if self.total_written_bytes > self.limits.max_unpacked_size {
+            anyhow::bail!(
+                "Refusing to extract: on-disk size would exceed {} bytes",
+                self.limits.max_unpacked_size
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Validate and resolve `entry_path` to a path under `output_root`, shared
+/// by [`ExtractGuard::resolve`] and [`AtomicExtractGuard::resolve`]
+pub(crate) fn resolve_under(output_root: &Path, entry_path: &str) -> Result<PathBuf> {
+    validate_path_components(entry_path)?;
+
+    let target = output_root.join(entry_path);
+    let parent = target.parent().unwrap_or(output_root);
+
+    std::fs::create_dir_all(parent)
+        .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    let resolved_parent = parent
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve directory: {}", parent.display()))?;
+
+    if !resolved_parent.starts_with(output_root) {
+        anyhow::bail!(
+            "Refusing to extract '{}': resolves outside the output directory",
+            entry_path
+        );
+    }
+
+    Ok(match target.file_name() {
+        Some(name) => resolved_parent.join(name),
+        None => resolved_parent,
+    })
+}
+
+/// Verify that `target` (a symlink's stored link text, not yet on disk)
+/// would resolve to somewhere under `output_root`, so a malicious archive
+/// can't use a symlink to point writes from a *later* entry outside the
+/// output directory.
+///
+/// `target` is resolved lexically relative to `link_path`'s parent rather
+/// than with `std::fs::canonicalize`, since the target commonly doesn't
+/// exist on disk yet (it may be dangling, or created by a later entry in
+/// the same archive) and `canonicalize` requires its argument to exist.
+fn validate_symlink_target_under(output_root: &Path, link_path: &Path, target: &str) -> Result<()> {
+    let mut resolved = link_path.parent().unwrap_or(output_root).to_path_buf();
+
+    for component in Path::new(target).components() {
+        match component {
+            Component::Normal(part) => resolved.push(part),
+            Component::CurDir => {}
+            Component::ParentDir => {
+                resolved.pop();
+            }
+            Component::RootDir | Component::Prefix(_) => {
+                anyhow::bail!(
+                    "Refusing to create symlink '{}': absolute target '{}' is not allowed",
+                    link_path.display(),
+                    target
+                );
+            }
+        }
+    }
+
+    if !resolved.starts_with(output_root) {
+        anyhow::bail!(
+            "Refusing to create symlink '{}': target '{}' resolves outside the output directory",
+            link_path.display(),
+            target
+        );
+    }
+
+    Ok(())
+}
+
+/// Thread-safe counterpart to [`ExtractGuard`], for `extract --jobs N`
+/// fanning work out across a rayon thread pool (see
+/// `crate::commands::shared::extract_parallel`). Every worker charges the
+/// same running totals via atomics, so the configured ceilings hold no
+/// matter how entries are interleaved across threads, exactly as they
+/// would for a single-threaded extract.
+pub struct AtomicExtractGuard {
+    output_root: PathBuf,
+    limits: ExtractLimits,
+    total_claimed_bytes: AtomicU64,
+    total_written_bytes: AtomicU64,
+    total_files: AtomicU64,
+}
+
+impl AtomicExtractGuard {
+    pub fn new(output: &Path, limits: ExtractLimits) -> Result<Self> {
+        std::fs::create_dir_all(output)
+            .with_context(|| format!("Failed to create output directory: {}", output.display()))?;
+        let output_root = output
+            .canonicalize()
+            .with_context(|| format!("Failed to resolve output directory: {}", output.display()))?;
+
+        Ok(Self {
+            output_root,
+            limits,
+            total_claimed_bytes: AtomicU64::new(0),
+            total_written_bytes: AtomicU64::new(0),
+            total_files: AtomicU64::new(0),
+        })
+    }
+
+    pub fn resolve(&self, entry_path: &str) -> Result<PathBuf> {
+        resolve_under(&self.output_root, entry_path)
+    }
+
+    /// Verify that a symlink about to be created at `link_path` wouldn't
+    /// point somewhere outside the output root once followed, mirroring
+    /// [`ExtractGuard::validate_symlink_target`]
+    pub fn validate_symlink_target(&self, link_path: &Path, target: &str) -> Result<()> {
+        validate_symlink_target_under(&self.output_root, link_path, target)
+    }
+
+    /// Account for one entry's claimed (uncompressed) size and file count
+    /// against the shared totals, erroring out if either would exceed its
+    /// configured ceiling. As with [`ExtractGuard::charge`], the ceiling
+    /// check happens after the add: under concurrent charges the running
+    /// total can briefly overshoot before the first caller to notice bails,
+    /// which is fine for a safety ceiling rather than a hard quota.
+    pub fn charge(&self, entry_size: u64) -> Result<()> {
+        let files = self.total_files.fetch_add(1, Ordering::SeqCst) + 1;
+        if files > self.limits.max_files {
+            anyhow::bail!(
+                "Refusing to extract: archive contains more than {} files",
+                self.limits.max_files
+            );
+        }
+
+        let bytes = checked_fetch_add(&self.total_claimed_bytes, entry_size)
+            .context("Refusing to extract: uncompressed size overflowed")?;
+        if bytes > self.limits.max_unpacked_size {
+            anyhow::bail!(
+                "Refusing to extract: uncompressed size would exceed {} bytes",
+                self.limits.max_unpacked_size
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Account for bytes actually flushed to disk against the shared
+    /// totals, mirroring [`ExtractGuard::charge_written`]
+    pub fn charge_written(&self, written_size: u64) -> Result<()> {
+        let bytes = checked_fetch_add(&self.total_written_bytes, written_size)
+            .context("Refusing to extract: on-disk size overflowed")?;
+        if bytes > self.limits.max_unpacked_size {
+            anyhow::bail!(
+                "Refusing to extract: on-disk size would exceed {} bytes",
+                self.limits.max_unpacked_size
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Atomically add `value` to `counter` and return the new total, failing
+/// instead of wrapping on overflow. `AtomicU64` has no native
+/// `checked_add`, so this emulates one with `fetch_update`.
+fn checked_fetch_add(counter: &AtomicU64, value: u64) -> Option<u64> {
+    counter
+        .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |current| {
+            current.checked_add(value)
+        })
+        .ok()
+        .map(|previous| previous + value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_rejects_parent_dir_component() {
+        assert!(validate_path_components("../escape.txt").is_err());
+        assert!(validate_path_components("a/../../escape.txt").is_err());
+    }
+
+    #[test]
+    fn test_rejects_absolute_path() {
+        assert!(validate_path_components("/etc/passwd").is_err());
+    }
+
+    #[test]
+    fn test_accepts_normal_relative_path() {
+        assert!(validate_path_components("a/b/c.txt").is_ok());
+    }
+
+    #[test]
+    fn test_rejects_current_dir_component() {
+        assert!(validate_path_components("./a.txt").is_err());
+        assert!(validate_path_components("a/./b.txt").is_err());
+    }
+
+    #[test]
+    fn test_guard_resolves_inside_output_root() {
+        let temp_dir = TempDir::new().unwrap();
+        let guard = ExtractGuard::new(temp_dir.path(), ExtractLimits::default()).unwrap();
+
+        let resolved = guard.resolve("sub/dir/file.txt").unwrap();
+        assert!(resolved.starts_with(temp_dir.path().canonicalize().unwrap()));
+    }
+
+    #[test]
+    fn test_guard_rejects_traversal_entry() {
+        let temp_dir = TempDir::new().unwrap();
+        let guard = ExtractGuard::new(temp_dir.path(), ExtractLimits::default()).unwrap();
+
+        assert!(guard.resolve("../outside.txt").is_err());
+    }
+
+    #[test]
+    fn test_charge_enforces_file_count_ceiling() {
+        let mut guard = ExtractGuard::new(
+            TempDir::new().unwrap().path(),
+            ExtractLimits {
+                max_unpacked_size: u64::MAX,
+                max_files: 2,
+            },
+        )
+        .unwrap();
+
+        assert!(guard.charge(0).is_ok());
+        assert!(guard.charge(0).is_ok());
+        assert!(guard.charge(0).is_err());
+    }
+
+    #[test]
+    fn test_charge_enforces_size_ceiling() {
+        let mut guard = ExtractGuard::new(
+            TempDir::new().unwrap().path(),
+            ExtractLimits {
+                max_unpacked_size: 100,
+                max_files: u64::MAX,
+            },
+        )
+        .unwrap();
+
+        assert!(guard.charge(60).is_ok());
+        assert!(guard.charge(60).is_err());
+    }
+
+    #[test]
+    fn test_charge_rejects_overflowing_size() {
+        let mut guard = ExtractGuard::new(TempDir::new().unwrap().path(), ExtractLimits::default())
+            .unwrap();
+
+        assert!(guard.charge(u64::MAX).is_err());
+    }
+
+    #[test]
+    fn test_charge_written_enforces_size_ceiling_independently_of_charge() {
+        let mut guard = ExtractGuard::new(
+            TempDir::new().unwrap().path(),
+            ExtractLimits {
+                max_unpacked_size: 100,
+                max_files: u64::MAX,
+            },
+        )
+        .unwrap();
+
+        assert!(guard.charge(60).is_ok());
+        assert!(guard.charge_written(60).is_ok());
+        assert!(guard.charge(30).is_ok());
+        assert!(guard.charge_written(60).is_err());
+    }
+
+    #[test]
+    fn test_guard_rejects_symlink_target_escaping_root() {
+        let temp_dir = TempDir::new().unwrap();
+        let guard = ExtractGuard::new(temp_dir.path(), ExtractLimits::default()).unwrap();
+
+        let link_path = guard.resolve("link").unwrap();
+        assert!(guard.validate_symlink_target(&link_path, "../../etc/passwd").is_err());
+        assert!(guard.validate_symlink_target(&link_path, "/etc/passwd").is_err());
+    }
+
+    #[test]
+    fn test_guard_accepts_symlink_target_inside_root() {
+        let temp_dir = TempDir::new().unwrap();
+        let guard = ExtractGuard::new(temp_dir.path(), ExtractLimits::default()).unwrap();
+
+        let link_path = guard.resolve("sub/link").unwrap();
+        assert!(guard.validate_symlink_target(&link_path, "../sibling.txt").is_ok());
+        assert!(guard.validate_symlink_target(&link_path, "dangling-target").is_ok());
+    }
+
+    #[test]
+    fn test_atomic_guard_resolves_inside_output_root() {
+        let temp_dir = TempDir::new().unwrap();
+        let guard = AtomicExtractGuard::new(temp_dir.path(), ExtractLimits::default()).unwrap();
+
+        let resolved = guard.resolve("sub/dir/file.txt").unwrap();
+        assert!(resolved.starts_with(temp_dir.path().canonicalize().unwrap()));
+    }
+
+    #[test]
+    fn test_atomic_guard_rejects_traversal_entry() {
+        let temp_dir = TempDir::new().unwrap();
+        let guard = AtomicExtractGuard::new(temp_dir.path(), ExtractLimits::default()).unwrap();
+
+        assert!(guard.resolve("../outside.txt").is_err());
+    }
+
+    #[test]
+    fn test_atomic_guard_enforces_ceilings_across_threads() {
+        let temp_dir = TempDir::new().unwrap();
+        let guard = std::sync::Arc::new(
+            AtomicExtractGuard::new(
+                temp_dir.path(),
+                ExtractLimits {
+                    max_unpacked_size: u64::MAX,
+                    max_files: 8,
+                },
+            )
+            .unwrap(),
+        );
+
+        let results: Vec<Result<()>> = std::thread::scope(|scope| {
+            (0..16)
+                .map(|_| {
+                    let guard = guard.clone();
+                    scope.spawn(move || guard.charge(0))
+                })
+                .collect::<Vec<_>>()
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect()
+        });
+
+        assert_eq!(results.iter().filter(|r| r.is_ok()).count(), 8);
+        assert_eq!(results.iter().filter(|r| r.is_err()).count(), 8);
+    }
+}
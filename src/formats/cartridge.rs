@@ -14,6 +14,18 @@ pub struct CartridgeArchive {
     cartridge: CartridgeCore,
 }
 
+impl CartridgeArchive {
+    /// Create a brand-new Cartridge archive at `path`, for callers (e.g.
+    /// `convert`) that need a fresh destination rather than an existing one
+    /// - `Archive::open` requires `path` to already exist.
+    pub fn create(path: &Path, slug: &str, title: &str) -> Result<Self> {
+        let cartridge = CartridgeCore::create_at(path, slug, title)
+            .with_context(|| format!("Failed to create Cartridge archive: {}", path.display()))?;
+
+        Ok(Self { cartridge })
+    }
+}
+
 impl Archive for CartridgeArchive {
     fn open(path: &Path) -> Result<Self> {
         let cartridge = CartridgeCore::open(path)
@@ -86,6 +98,8 @@ impl Archive for CartridgeArchive {
                         compression_method: "none".to_string(), // Cartridge doesn't expose compression info per file
                         modified: Some(metadata.modified_at),
                         crc32: None,
+                        // Cartridge's page store has no concept of Unix mode/ownership
+                        ..Default::default()
                     });
                 }
                 Err(_) => continue,
@@ -101,24 +115,30 @@ impl Archive for CartridgeArchive {
             .with_context(|| format!("Failed to read file '{}' from Cartridge archive", path))
     }
 
-    fn extract(&mut self, output: &Path, files: Option<&[String]>) -> Result<()> {
+    fn extract(
+        &mut self,
+        output: &Path,
+        files: Option<&[String]>,
+        limits: crate::extract::ExtractLimits,
+    ) -> Result<()> {
         let files_to_extract = match files {
             Some(f) => f.to_vec(),
             None => self.cartridge.list("")?,
         };
 
-        for file_path in files_to_extract {
-            let data = self.read_file(&file_path)?;
-            let output_path = output.join(&file_path);
-
-            // Create parent directories
-            if let Some(parent) = output_path.parent() {
-                std::fs::create_dir_all(parent)
-                    .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
-            }
+        let mut guard = crate::extract::ExtractGuard::new(output, limits)?;
 
-            std::fs::write(&output_path, data)
+        for file_path in files_to_extract {
+            let data = self
+                .cartridge
+                .read(&file_path)
+                .with_context(|| format!("Failed to read file '{}' from Cartridge archive", file_path))?;
+            guard.charge(data.len() as u64)?;
+
+            let output_path = guard.resolve(&file_path)?;
+            std::fs::write(&output_path, &data)
                 .with_context(|| format!("Failed to write file: {}", output_path.display()))?;
+            guard.charge_written(data.len() as u64)?;
         }
 
         Ok(())
@@ -145,6 +165,10 @@ impl Archive for CartridgeArchive {
     fn search(&mut self, pattern: &str, case_insensitive: bool) -> Result<Vec<SearchResult>> {
         let mut results = Vec::new();
         let files = self.cartridge.list("")?;
+        let options = crate::search::SearchOptions {
+            case_insensitive,
+            ..Default::default()
+        };
 
         for file_path in files {
             let data = match self.cartridge.read(&file_path) {
@@ -157,29 +181,15 @@ impl Archive for CartridgeArchive {
                 Err(_) => continue, // Skip binary files
             };
 
-            for (line_number, line) in content.lines().enumerate() {
-                let matches = if case_insensitive {
-                    line.to_lowercase().contains(&pattern.to_lowercase())
-                } else {
-                    line.contains(pattern)
-                };
-
-                if matches {
-                    let match_offset = if case_insensitive {
-                        line.to_lowercase()
-                            .find(&pattern.to_lowercase())
-                            .unwrap_or(0)
-                    } else {
-                        line.find(pattern).unwrap_or(0)
-                    };
-
-                    results.push(SearchResult {
-                        file_path: file_path.clone(),
-                        line_number: line_number + 1,
-                        line_content: line.to_string(),
-                        match_offset,
-                    });
-                }
+            for m in crate::search::find_matches(&content, pattern, &options)? {
+                results.push(SearchResult {
+                    file_path: file_path.clone(),
+                    line_number: m.line_number,
+                    line_content: m.line_content,
+                    match_spans: m.match_spans,
+                    before: m.before.clone(),
+                    after: m.after.clone(),
+                });
             }
         }
 
@@ -216,34 +226,22 @@ impl QueryableArchive for CartridgeArchive {
         let all_files = self.cartridge.list("")?;
         Ok(all_files
             .into_iter()
-            .filter(|f| {
-                f.ends_with(".db") || f.ends_with(".sqlite") || f.ends_with(".sqlite3")
-            })
+            .filter(|f| super::traits::is_database_path(f))
             .collect())
     }
 
     fn query(&mut self, database: &str, sql: &str, format: OutputFormat) -> Result<String> {
-        // Cartridge has VFS support, but the API isn't exposed in the high-level wrapper
-        // For now, we can extract the database temporarily and query it
-        // TODO: Use VFS integration when available in cartridge-rs API
-
-        // Check if database exists
         if !self.cartridge.exists(database)? {
             anyhow::bail!("Database '{}' not found in Cartridge archive", database);
         }
 
-        // Read database file
+        // cartridge_rs has no byte-range reader to build a real VFS on top
+        // of (see `crate::sql::backend`'s module docs), so this still fully
+        // materializes the database - it only avoids the temp-file copy the
+        // old extract-to-disk approach paid on top of that.
         let db_data = self.cartridge.read(database)?;
-
-        // Create temporary file
-        let temp_dir = tempfile::tempdir().context("Failed to create temporary directory")?;
-        let temp_db_path = temp_dir.path().join("temp.db");
-        std::fs::write(&temp_db_path, db_data)
-            .context("Failed to write temporary database file")?;
-
-        // Open with rusqlite
-        let conn = rusqlite::Connection::open(&temp_db_path)
-            .context("Failed to open temporary database")?;
+        let conn = crate::sql::backend::open_database(crate::sql::backend::InMemoryBackend::new(db_data))
+            .with_context(|| format!("Failed to open database '{}'", database))?;
 
         execute_query(&conn, sql, format)
     }
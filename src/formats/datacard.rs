@@ -74,6 +74,8 @@ impl Archive for DataCardArchive {
             } else {
                 None
             },
+            // DataCard is a single compressed document with no POSIX metadata
+            ..Default::default()
         }])
     }
 
@@ -86,15 +88,20 @@ impl Archive for DataCardArchive {
         }
     }
 
-    fn extract(&mut self, output: &Path, _files: Option<&[String]>) -> Result<()> {
+    fn extract(
+        &mut self,
+        output: &Path,
+        _files: Option<&[String]>,
+        limits: crate::extract::ExtractLimits,
+    ) -> Result<()> {
         // Extract the card payload (still compressed with BytePunch)
-        let output_path = output.join("document.card");
-
-        std::fs::create_dir_all(output)
-            .with_context(|| format!("Failed to create directory: {}", output.display()))?;
+        let mut guard = crate::extract::ExtractGuard::new(output, limits)?;
+        guard.charge(self.card.payload.len() as u64)?;
+        let output_path = guard.resolve("document.card")?;
 
         self.card.save(&output_path)
             .with_context(|| format!("Failed to write DataCard: {}", output_path.display()))?;
+        guard.charge_written(self.card.payload.len() as u64)?;
 
         Ok(())
     }
@@ -113,41 +120,23 @@ impl Archive for DataCardArchive {
     }
 
     fn search(&mut self, pattern: &str, case_insensitive: bool) -> Result<Vec<SearchResult>> {
-        // DataCard stores compressed data, so we can't search without decompression
-        // This would require a Dictionary, which we don't have in the Archive trait
-        //
-        // For now, search in the compressed payload (won't match CML content)
-        let mut results = Vec::new();
-
-        // Convert payload to string (likely to fail for binary data, but worth a try)
-        if let Ok(content) = String::from_utf8(self.card.payload.clone()) {
-            for (line_number, line) in content.lines().enumerate() {
-                let matches = if case_insensitive {
-                    line.to_lowercase().contains(&pattern.to_lowercase())
-                } else {
-                    line.contains(pattern)
-                };
-
-                if matches {
-                    let match_offset = if case_insensitive {
-                        line.to_lowercase()
-                            .find(&pattern.to_lowercase())
-                            .unwrap_or(0)
-                    } else {
-                        line.find(pattern).unwrap_or(0)
-                    };
-
-                    results.push(SearchResult {
-                        file_path: "document.cml".to_string(),
-                        line_number: line_number + 1,
-                        line_content: line.to_string(),
-                        match_offset,
-                    });
-                }
+        match self.decompress_for_search() {
+            Ok(Some(cml)) => self.search_text(&cml, pattern, case_insensitive),
+            Ok(None) => {
+                eprintln!(
+                    "Warning: DataCard '{}' has no dict_version recorded; searching the raw BytePunch payload instead of the decompressed document",
+                    self.path.display()
+                );
+                self.search_compressed_payload(pattern, case_insensitive)
+            }
+            Err(e) => {
+                eprintln!(
+                    "Warning: could not decompress DataCard '{}' for search ({e:#}); falling back to the raw BytePunch payload",
+                    self.path.display()
+                );
+                self.search_compressed_payload(pattern, case_insensitive)
             }
         }
-
-        Ok(results)
     }
 
     fn format_name(&self) -> &'static str {
@@ -155,4 +144,61 @@ impl Archive for DataCardArchive {
     }
 }
 
+impl DataCardArchive {
+    /// Decompress this card's CML document for searching, resolving its
+    /// dictionary via [`super::dictionary::resolve`] against the
+    /// `dict_version` recorded in the card's metadata.
+    ///
+    /// Returns `Ok(None)` when the card has no `dict_version` recorded (so
+    /// there's nothing to resolve); returns `Err` when a version is
+    /// recorded but the dictionary can't be found or the payload fails to
+    /// decompress against it.
+    fn decompress_for_search(&self) -> Result<Option<String>> {
+        let Some(dict_version) = self.card.metadata.dict_version.as_deref() else {
+            return Ok(None);
+        };
+
+        let dictionary = super::dictionary::resolve(dict_version)?;
+        let cml = self
+            .card
+            .to_cml(&dictionary)
+            .with_context(|| format!("Failed to decompress DataCard: {}", self.path.display()))?;
+
+        Ok(Some(cml))
+    }
+
+    /// Run a pattern search against already-decompressed CML text
+    fn search_text(&self, content: &str, pattern: &str, case_insensitive: bool) -> Result<Vec<SearchResult>> {
+        let options = crate::search::SearchOptions {
+            case_insensitive,
+            ..Default::default()
+        };
+
+        let mut results = Vec::new();
+        for m in crate::search::find_matches(content, pattern, &options)? {
+            results.push(SearchResult {
+                file_path: "document.cml".to_string(),
+                line_number: m.line_number,
+                line_content: m.line_content,
+                match_spans: m.match_spans,
+                before: m.before.clone(),
+                after: m.after.clone(),
+            });
+        }
+
+        Ok(results)
+    }
+
+    /// Best-effort fallback when the card's dictionary isn't available:
+    /// search the still-compressed payload directly. This won't match CML
+    /// content reliably, but degrades gracefully instead of refusing to
+    /// search at all.
+    fn search_compressed_payload(&self, pattern: &str, case_insensitive: bool) -> Result<Vec<SearchResult>> {
+        match String::from_utf8(self.card.payload.clone()) {
+            Ok(content) => self.search_text(&content, pattern, case_insensitive),
+            Err(_) => Ok(Vec::new()),
+        }
+    }
+}
+
 // DataCard is immutable, so no MutableArchive implementation
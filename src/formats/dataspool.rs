@@ -1,45 +1,167 @@
 use anyhow::{Context, Result};
 use dataspool_rs::{SpoolReader, SpoolBuilder};
-use std::path::Path;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
 
+use super::dataspool_split::{split_suffix, SplitSpoolReader};
+use super::layered::{LayerFlags, LayeredReader};
 use super::traits::{Archive, ArchiveInfo, FileEntry, MutableArchive, SearchResult};
+use crate::dedup::chunker::ChunkerConfig;
+use crate::dedup::{ChunkStore, DedupArchive};
+
+/// Backs a [`DataSpoolArchive`] with either a single-file `dataspool_rs`
+/// reader or the multi-part reader from [`super::dataspool_split`], so the
+/// rest of this file can read cards without caring which one is backing a
+/// given `.spool` path.
+enum SpoolBackend {
+    Single(SpoolReader),
+    Split(SplitSpoolReader),
+}
+
+impl SpoolBackend {
+    fn card_count(&self) -> usize {
+        match self {
+            SpoolBackend::Single(r) => r.card_count(),
+            SpoolBackend::Split(r) => r.card_count(),
+        }
+    }
+
+    /// Length of every card in order; `dataspool_rs::SpoolReader::entries()`
+    /// and `SplitSpoolReader::card_length` expose this shape differently,
+    /// so this is the one place that flattens them both to `Vec<u64>`.
+    fn card_lengths(&self) -> Vec<u64> {
+        match self {
+            SpoolBackend::Single(r) => r.entries().iter().map(|e| e.length as u64).collect(),
+            SpoolBackend::Split(r) => (0..r.card_count())
+                .map(|i| r.card_length(i).unwrap_or(0))
+                .collect(),
+        }
+    }
+
+    fn read_card(&self, index: usize) -> Result<Vec<u8>> {
+        match self {
+            SpoolBackend::Single(r) => r.read_card(index),
+            SpoolBackend::Split(r) => r.read_card(index),
+        }
+    }
+}
+
+/// Parse a card's virtual path (`card_00000`, `dcard_00000`, or a bare
+/// index) back into its numeric index
+fn parse_card_index(path: &str) -> Result<usize> {
+    let idx_str = path
+        .strip_prefix("dcard_")
+        .or_else(|| path.strip_prefix("card_"))
+        .unwrap_or(path);
+    idx_str
+        .parse::<usize>()
+        .with_context(|| format!("Invalid card path: {path}"))
+}
+
+/// Environment variable naming a file holding the X25519 private key to use
+/// when opening a layer-encrypted `.spool` file, the same out-of-band
+/// convention [`crate::crypto::password`] uses for passphrases.
+pub const X25519_KEY_ENV_VAR: &str = "STRATEGOS_X25519_KEY";
 
 /// Wrapper for DataSpool archives (.spool)
 ///
 /// DataSpool is an append-only format for bundling multiple items (cards, images, etc.)
 /// with a byte-offset index for random access.
 pub struct DataSpoolArchive {
-    reader: SpoolReader,
+    reader: SpoolBackend,
     path: std::path::PathBuf,
+    /// Compression/encryption layer status, if `path` started with a
+    /// [`super::layered`] header
+    layer_flags: Option<LayerFlags>,
+    /// Set when `open` had to stage decrypted/decompressed bytes in a
+    /// sibling file for `SpoolReader` to open; removed when this archive
+    /// is dropped.
+    layer_temp_path: Option<std::path::PathBuf>,
+}
+
+impl Drop for DataSpoolArchive {
+    fn drop(&mut self) {
+        if let Some(temp_path) = &self.layer_temp_path {
+            let _ = std::fs::remove_file(temp_path);
+        }
+    }
 }
 
 impl Archive for DataSpoolArchive {
     fn open(path: &Path) -> Result<Self> {
-        let reader = SpoolReader::open(path)
-            .with_context(|| format!("Failed to open DataSpool archive: {}", path.display()))?;
+        if super::detection::is_split_dataspool(path) {
+            return Self::open_split(path);
+        }
+
+        let raw = std::fs::read(path)
+            .with_context(|| format!("Failed to read DataSpool archive: {}", path.display()))?;
+
+        let layer_flags = LayeredReader::peek_flags(&raw);
+
+        let (reader, layer_temp_path) = match layer_flags {
+            None => (
+                SpoolBackend::Single(SpoolReader::open(path).with_context(|| {
+                    format!("Failed to open DataSpool archive: {}", path.display())
+                })?),
+                None,
+            ),
+            Some(flags) => {
+                let mut unwrapper = LayeredReader::new();
+                if flags.encrypted {
+                    let key_path = std::env::var(X25519_KEY_ENV_VAR).with_context(|| {
+                        format!(
+                            "`{}` is layer-encrypted but {} is not set to a recipient private key path",
+                            path.display(),
+                            X25519_KEY_ENV_VAR
+                        )
+                    })?;
+                    let keypair = crate::crypto::x25519::X25519KeyPair::load_private(&key_path)?;
+                    unwrapper = unwrapper.with_recipient_secret(keypair.secret().clone());
+                }
+                let plaintext = unwrapper.unwrap(&raw)?;
+
+                // `SpoolReader` only opens from a path, so the peeled
+                // plaintext has to be staged in a sibling file - removed
+                // again once this archive is dropped (see `Drop` above).
+                let temp_path = path.with_extension("spool.layer-tmp");
+                std::fs::write(&temp_path, &plaintext).with_context(|| {
+                    format!("Failed to stage decrypted DataSpool at `{}`", temp_path.display())
+                })?;
+                let reader = SpoolBackend::Single(SpoolReader::open(&temp_path).with_context(
+                    || format!("Failed to open decrypted DataSpool: {}", path.display()),
+                )?);
+                (reader, Some(temp_path))
+            }
+        };
 
         Ok(Self {
             reader,
             path: path.to_path_buf(),
+            layer_flags,
+            layer_temp_path,
         })
     }
 
     fn info(&mut self) -> Result<ArchiveInfo> {
         let entry_count = self.reader.card_count();
-        let entries = self.reader.entries();
-
-        let mut total_size = 0u64;
-        for entry in entries {
-            total_size += entry.length as u64;
-        }
+        let total_size: u64 = self.reader.card_lengths().iter().sum();
 
         // DataSpool stores pre-compressed data, so compressed size ≈ total size
+        // by default; a card ever written through `write_file_deduped` gets
+        // a real ratio computed from its chunk store instead of this 1.0.
         let compressed_size = total_size;
+        let dedup = read_dedup_sidecar(&self.path);
+        let dedup_ratio = dedup.as_ref().and_then(|sidecar| dedup_ratio(&self.path, sidecar));
 
         let metadata = serde_json::json!({
             "card_count": entry_count,
             "format": "dataspool",
             "index_entries": entry_count,
+            "layer_compressed": self.layer_flags.map(|f| f.compressed),
+            "layer_encrypted": self.layer_flags.map(|f| f.encrypted),
+            "dedup_card_count": dedup.as_ref().map(|s| s.recipes.len()),
+            "dedup_unique_chunks": dedup.as_ref().map(|s| s.chunk_index.len()),
         });
 
         Ok(ArchiveInfo {
@@ -48,23 +170,26 @@ impl Archive for DataSpoolArchive {
             entry_count,
             total_size,
             compressed_size,
-            compression_ratio: 1.0, // Data is pre-compressed
+            compression_ratio: dedup_ratio.unwrap_or(1.0),
             metadata,
         })
     }
 
     fn list_files(&mut self) -> Result<Vec<FileEntry>> {
-        let entries = self.reader.entries();
+        let lengths = self.reader.card_lengths();
+        let sidecar = read_checksum_sidecar(&self.path);
         let mut file_entries = Vec::new();
 
-        for (index, entry) in entries.iter().enumerate() {
+        for (index, length) in lengths.iter().enumerate() {
             file_entries.push(FileEntry {
                 path: format!("card_{:05}", index), // Virtual path for each card
-                size: entry.length as u64,
-                compressed_size: entry.length as u64, // Pre-compressed
+                size: *length,
+                compressed_size: *length, // Pre-compressed
                 compression_method: "bytepunch".to_string(),
                 modified: None,
-                crc32: None,
+                crc32: sidecar.as_ref().and_then(|s| s.card_crc32.get(index).copied()),
+                // Cards are virtual paths into the spool blob; no POSIX metadata exists
+                ..Default::default()
             });
         }
 
@@ -72,47 +197,45 @@ impl Archive for DataSpoolArchive {
     }
 
     fn read_file(&mut self, path: &str) -> Result<Vec<u8>> {
-        // Parse card index from path (e.g., "card_00000" -> 0)
-        let index = if let Some(idx_str) = path.strip_prefix("card_") {
-            idx_str.parse::<usize>()
-                .with_context(|| format!("Invalid card path: {}", path))?
-        } else {
-            // Try parsing as direct index
-            path.parse::<usize>()
-                .with_context(|| format!("Invalid card index: {}", path))?
-        };
+        let index = parse_card_index(path)?;
 
         self.reader
             .read_card(index)
             .with_context(|| format!("Failed to read card {} from DataSpool", index))
     }
 
-    fn extract(&mut self, output: &Path, files: Option<&[String]>) -> Result<()> {
+    fn extract(
+        &mut self,
+        output: &Path,
+        files: Option<&[String]>,
+        limits: crate::extract::ExtractLimits,
+    ) -> Result<()> {
         let card_count = self.reader.card_count();
+        let mut guard = crate::extract::ExtractGuard::new(output, limits)?;
 
         // If specific files requested, extract those; otherwise extract all
         if let Some(files) = files {
             for file_path in files {
                 let data = self.read_file(file_path)?;
-                let output_path = output.join(file_path);
-
-                std::fs::create_dir_all(output)
-                    .with_context(|| format!("Failed to create directory: {}", output.display()))?;
+                let size = data.len() as u64;
+                guard.charge(size)?;
+                let output_path = guard.resolve(file_path)?;
 
                 std::fs::write(&output_path, data)
                     .with_context(|| format!("Failed to write file: {}", output_path.display()))?;
+                guard.charge_written(size)?;
             }
         } else {
             // Extract all cards
-            std::fs::create_dir_all(output)
-                .with_context(|| format!("Failed to create directory: {}", output.display()))?;
-
             for index in 0..card_count {
                 let data = self.reader.read_card(index)?;
-                let output_path = output.join(format!("card_{:05}.card", index));
+                let size = data.len() as u64;
+                guard.charge(size)?;
+                let output_path = guard.resolve(&format!("card_{:05}.card", index))?;
 
                 std::fs::write(&output_path, data)
                     .with_context(|| format!("Failed to write file: {}", output_path.display()))?;
+                guard.charge_written(size)?;
             }
         }
 
@@ -120,21 +243,21 @@ impl Archive for DataSpoolArchive {
     }
 
     fn verify(&mut self) -> Result<bool> {
-        // Verify we can read all cards successfully
-        let card_count = self.reader.card_count();
-
-        for index in 0..card_count {
-            if let Err(_) = self.reader.read_card(index) {
-                return Ok(false);
-            }
-        }
-
-        Ok(true)
+        // Beyond just decoding every card, recompute and compare checksums
+        // against the sidecar recorded at build time (see `verify_report`);
+        // falls back to a pure decode-smoke-test when no sidecar exists,
+        // e.g. for spools built before checksum support was added.
+        let report = self.verify_report()?;
+        Ok(report.failed_indexes.is_empty() && report.whole_image_sha256_matches != Some(false))
     }
 
     fn search(&mut self, pattern: &str, case_insensitive: bool) -> Result<Vec<SearchResult>> {
         let mut results = Vec::new();
         let card_count = self.reader.card_count();
+        let options = crate::search::SearchOptions {
+            case_insensitive,
+            ..Default::default()
+        };
 
         for index in 0..card_count {
             let data = match self.reader.read_card(index) {
@@ -150,29 +273,15 @@ impl Archive for DataSpoolArchive {
 
             let card_path = format!("card_{:05}", index);
 
-            for (line_number, line) in content.lines().enumerate() {
-                let matches = if case_insensitive {
-                    line.to_lowercase().contains(&pattern.to_lowercase())
-                } else {
-                    line.contains(pattern)
-                };
-
-                if matches {
-                    let match_offset = if case_insensitive {
-                        line.to_lowercase()
-                            .find(&pattern.to_lowercase())
-                            .unwrap_or(0)
-                    } else {
-                        line.find(pattern).unwrap_or(0)
-                    };
-
-                    results.push(SearchResult {
-                        file_path: card_path.clone(),
-                        line_number: line_number + 1,
-                        line_content: line.to_string(),
-                        match_offset,
-                    });
-                }
+            for m in crate::search::find_matches(&content, pattern, &options)? {
+                results.push(SearchResult {
+                    file_path: card_path.clone(),
+                    line_number: m.line_number,
+                    line_content: m.line_content,
+                    match_spans: m.match_spans,
+                    before: m.before.clone(),
+                    after: m.after.clone(),
+                });
             }
         }
 
@@ -184,10 +293,437 @@ impl Archive for DataSpoolArchive {
     }
 }
 
+/// Per-card CRC32s plus a whole-image SHA-256, recorded alongside a
+/// `.spool` file any time this crate builds one (`write_file`, `recover`)
+///
+/// `dataspool_rs`'s on-disk index format isn't public from this crate, so
+/// checksums can't be threaded through `SpoolBuilder`/`SpoolReader`
+/// themselves; this sidecar JSON file is the equivalent disc-image-tool
+/// idea (CRC32 per block, a strong hash over the whole image) kept
+/// entirely in code we control.
+#[derive(Serialize, Deserialize)]
+struct ChecksumSidecar {
+    card_crc32: Vec<u32>,
+    whole_image_sha256: String,
+}
+
+fn checksum_sidecar_path(path: &Path) -> PathBuf {
+    path.with_extension("spool.cardsums.json")
+}
+
+fn write_checksum_sidecar(path: &Path, cards: &[Vec<u8>]) -> Result<()> {
+    let card_crc32 = cards.iter().map(|c| crc32fast::hash(c)).collect();
+
+    let mut hasher = Sha256::new();
+    for card in cards {
+        hasher.update(card);
+    }
+    let whole_image_sha256 = hex::encode(hasher.finalize());
+
+    let sidecar = ChecksumSidecar {
+        card_crc32,
+        whole_image_sha256,
+    };
+    let json = serde_json::to_string(&sidecar).context("Failed to serialize checksum sidecar")?;
+    std::fs::write(checksum_sidecar_path(path), json)
+        .context("Failed to write checksum sidecar")?;
+    Ok(())
+}
+
+fn read_checksum_sidecar(path: &Path) -> Option<ChecksumSidecar> {
+    let raw = std::fs::read_to_string(checksum_sidecar_path(path)).ok()?;
+    serde_json::from_str(&raw).ok()
+}
+
+/// Content-defined dedup state for cards written through
+/// [`DataSpoolArchive::write_file_deduped`]: each such card's ordered
+/// reconstruction recipe (hex-encoded chunk digests), plus where every
+/// unique chunk lives in the companion chunk-store spool at
+/// [`chunk_store_path`].
+///
+/// This is deliberately a separate virtual card stream from the one
+/// `self.reader`/`write_file` manage - `SpoolReader`/`SpoolBuilder` only
+/// ever deal in whole opaque blobs, so a deduped card's chunk list has
+/// nowhere to live inside `dataspool_rs`'s own index, the same reason the
+/// checksum sidecar above exists.
+#[derive(Serialize, Deserialize, Default)]
+struct DedupSidecar {
+    /// One entry per card written via `write_file_deduped`, each the
+    /// ordered list of hex-encoded chunk digests that reconstructs it.
+    recipes: Vec<Vec<String>>,
+    /// Hex-encoded chunk digest -> that chunk's card index in the
+    /// chunk-store spool.
+    chunk_index: std::collections::HashMap<String, usize>,
+}
+
+fn dedup_sidecar_path(path: &Path) -> PathBuf {
+    path.with_extension("spool.dedup.json")
+}
+
+/// Path of the append-only spool that physically stores each unique chunk
+/// as one card, indexed by [`DedupSidecar::chunk_index`]
+fn chunk_store_path(path: &Path) -> PathBuf {
+    path.with_extension("spool.chunks")
+}
+
+fn read_dedup_sidecar(path: &Path) -> Option<DedupSidecar> {
+    let raw = std::fs::read_to_string(dedup_sidecar_path(path)).ok()?;
+    serde_json::from_str(&raw).ok()
+}
+
+fn write_dedup_sidecar(path: &Path, sidecar: &DedupSidecar) -> Result<()> {
+    let json = serde_json::to_string(sidecar).context("Failed to serialize dedup sidecar")?;
+    std::fs::write(dedup_sidecar_path(path), json).context("Failed to write dedup sidecar")?;
+    Ok(())
+}
+
+/// Rebuild the chunk-store spool at `store_path` with `new_chunks`
+/// appended after whatever it already holds - the same
+/// read-everything-into-a-temp-file-then-rename approach `write_file`
+/// above uses, since `SpoolBuilder` only ever creates a fresh spool rather
+/// than appending in place.
+fn append_chunks_to_store(store_path: &Path, new_chunks: &[Vec<u8>]) -> Result<()> {
+    let temp_path = store_path.with_extension("chunks.tmp");
+    let mut builder = SpoolBuilder::new(&temp_path)
+        .context("Failed to create chunk-store spool builder")?;
+
+    if store_path.exists() {
+        let mut existing =
+            SpoolReader::open(store_path).context("Failed to open existing chunk store")?;
+        for index in 0..existing.card_count() {
+            builder.add_card(&existing.read_card(index)?)?;
+        }
+    }
+
+    for chunk in new_chunks {
+        builder.add_card(chunk)?;
+    }
+    builder.finalize()?;
+
+    std::fs::rename(&temp_path, store_path).context("Failed to replace chunk-store spool")?;
+    Ok(())
+}
+
+fn decode_digest(digest_hex: &str) -> Result<[u8; 32]> {
+    let bytes = hex::decode(digest_hex).context("Invalid chunk digest hex")?;
+    bytes
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Chunk digest `{digest_hex}` has the wrong length"))
+}
+
+/// Real compression/dedup ratio for `path`'s deduped cards: total bytes
+/// those recipes reference divided by the unique bytes actually stored in
+/// the chunk store. `None` if there's no chunk store yet to measure
+/// against.
+fn dedup_ratio(path: &Path, sidecar: &DedupSidecar) -> Option<f64> {
+    if sidecar.recipes.is_empty() {
+        return None;
+    }
+
+    let store = SpoolReader::open(&chunk_store_path(path)).ok()?;
+    let lengths: Vec<u64> = store.entries().iter().map(|e| e.length as u64).collect();
+    let unique_bytes: u64 = lengths.iter().sum();
+    if unique_bytes == 0 {
+        return None;
+    }
+
+    let total_bytes: u64 = sidecar
+        .recipes
+        .iter()
+        .flatten()
+        .filter_map(|digest_hex| sidecar.chunk_index.get(digest_hex))
+        .filter_map(|&index| lengths.get(index))
+        .sum();
+
+    Some(total_bytes as f64 / unique_bytes as f64)
+}
+
+/// Magic bytes at the start of a `.spool` file
+const SPOOL_MAGIC: &[u8; 4] = b"SP01";
+/// Each card frame is prefixed with a `u32` LE length, matching what
+/// `SpoolBuilder::add_card` writes
+const FRAME_HEADER_LEN: usize = 4;
+/// Guard against a garbage length prefix sending the scan off into the
+/// weeds instead of recognizing corruption
+const MAX_SANE_FRAME_LEN: u64 = 4 * 1024 * 1024 * 1024;
+
+/// Report produced by [`DataSpoolArchive::recover`] describing how much of
+/// a truncated or corrupted `.spool` file was salvaged
+pub struct SalvageReport {
+    pub recovered_cards: usize,
+    pub bytes_parsed: u64,
+    /// Byte offset where salvage gave up; `None` if the whole file parsed
+    /// as a clean sequence of frames
+    pub corruption_offset: Option<u64>,
+}
+
+/// Structured result of [`DataSpoolArchive::verify_report`]
+pub struct VerifyReport {
+    pub cards_checked: usize,
+    /// Indexes of cards that failed to decode or whose recomputed CRC32
+    /// didn't match the sidecar
+    pub failed_indexes: Vec<usize>,
+    /// `None` when no checksum sidecar exists to compare the whole image
+    /// against (e.g. a spool built before checksum support existed)
+    pub whole_image_sha256_matches: Option<bool>,
+}
+
+impl DataSpoolArchive {
+    /// Open a DataSpool split across numbered parts (`archive.spool.001`,
+    /// `.002`, …), given either the unsuffixed stem or any one of its parts.
+    ///
+    /// Called directly from [`Archive::open`] once
+    /// [`super::detection::is_split_dataspool`] recognizes `path` as part of
+    /// a split set, so callers never need to know which backend they got -
+    /// compression/encryption layers aren't wired up for split sets yet,
+    /// the same "not wired up on this path yet" limitation `open` already
+    /// has for writes (see [`MutableArchive::write_file`] below).
+    fn open_split(path: &Path) -> Result<Self> {
+        let stem = match split_suffix(path) {
+            Some((stem, _number)) => stem,
+            None => path.display().to_string(),
+        };
+
+        let reader = SplitSpoolReader::open(&stem)
+            .with_context(|| format!("Failed to open split DataSpool set: {}", path.display()))?;
+
+        Ok(Self {
+            reader: SpoolBackend::Split(reader),
+            path: path.to_path_buf(),
+            layer_flags: None,
+            layer_temp_path: None,
+        })
+    }
+
+    /// Recompute every card's CRC32 (and the whole image's SHA-256) and
+    /// compare against the sidecar recorded at build time, reporting
+    /// exactly which card indexes are corrupt instead of a flat bool.
+    ///
+    /// Falls back to a pure decode check when no sidecar exists -
+    /// `failed_indexes` still reports unreadable cards, but
+    /// `whole_image_sha256_matches` is `None` since there's nothing to
+    /// compare against.
+    pub fn verify_report(&mut self) -> Result<VerifyReport> {
+        let card_count = self.reader.card_count();
+        let sidecar = read_checksum_sidecar(&self.path);
+        let mut failed_indexes = Vec::new();
+        let mut cards = Vec::with_capacity(card_count);
+
+        for index in 0..card_count {
+            match self.reader.read_card(index) {
+                Ok(data) => {
+                    if let Some(expected) =
+                        sidecar.as_ref().and_then(|s| s.card_crc32.get(index).copied())
+                    {
+                        if crc32fast::hash(&data) != expected {
+                            failed_indexes.push(index);
+                        }
+                    }
+                    cards.push(data);
+                }
+                Err(_) => failed_indexes.push(index),
+            }
+        }
+
+        let whole_image_sha256_matches = sidecar.as_ref().map(|sidecar| {
+            let mut hasher = Sha256::new();
+            for card in &cards {
+                hasher.update(card);
+            }
+            hex::encode(hasher.finalize()) == sidecar.whole_image_sha256
+        });
+
+        Ok(VerifyReport {
+            cards_checked: card_count,
+            failed_indexes,
+            whole_image_sha256_matches,
+        })
+    }
+
+    /// Extract only the cards whose virtual `card_NNNNN` path matches
+    /// `patterns`, instead of `Archive::extract`'s all-or-an-explicit-list
+    /// choice.
+    ///
+    /// This is a `DataSpoolArchive`-specific entry point rather than a
+    /// change to `Archive::extract`'s signature: threading pattern
+    /// filtering through the shared trait would force every other format
+    /// (Engram, Cartridge, Tar, Zip, DataCard) to grow the same parameter
+    /// for a feature only asked for here, where "pull just the cards
+    /// matching this prefix out of a large spool" is the actual need.
+    pub fn extract_matching(
+        &mut self,
+        output: &Path,
+        patterns: &crate::patterns::MatchList,
+        limits: crate::extract::ExtractLimits,
+    ) -> Result<()> {
+        let card_count = self.reader.card_count();
+        let mut guard = crate::extract::ExtractGuard::new(output, limits)?;
+
+        for index in 0..card_count {
+            let card_path = format!("card_{:05}", index);
+            if !patterns.is_match(&card_path) {
+                continue;
+            }
+
+            let data = self.reader.read_card(index)?;
+            let size = data.len() as u64;
+            guard.charge(size)?;
+            let output_path = guard.resolve(&format!("{card_path}.card"))?;
+
+            std::fs::write(&output_path, data)
+                .with_context(|| format!("Failed to write file: {}", output_path.display()))?;
+            guard.charge_written(size)?;
+        }
+
+        Ok(())
+    }
+
+    /// Search only the cards whose virtual `card_NNNNN` path matches
+    /// `patterns`; see [`Self::extract_matching`] for why this is a
+    /// dedicated method rather than a trait change.
+    pub fn search_matching(
+        &mut self,
+        pattern: &str,
+        case_insensitive: bool,
+        patterns: &crate::patterns::MatchList,
+    ) -> Result<Vec<SearchResult>> {
+        if patterns.is_empty() {
+            return self.search(pattern, case_insensitive);
+        }
+
+        let mut results = Vec::new();
+        let card_count = self.reader.card_count();
+        let options = crate::search::SearchOptions {
+            case_insensitive,
+            ..Default::default()
+        };
+
+        for index in 0..card_count {
+            let card_path = format!("card_{:05}", index);
+            if !patterns.is_match(&card_path) {
+                continue;
+            }
+
+            let data = match self.reader.read_card(index) {
+                Ok(d) => d,
+                Err(_) => continue,
+            };
+
+            let content = match String::from_utf8(data) {
+                Ok(s) => s,
+                Err(_) => continue,
+            };
+
+            for m in crate::search::find_matches(&content, pattern, &options)? {
+                results.push(SearchResult {
+                    file_path: card_path.clone(),
+                    line_number: m.line_number,
+                    line_content: m.line_content,
+                    match_spans: m.match_spans,
+                    before: m.before.clone(),
+                    after: m.after.clone(),
+                });
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Reconstruct a truncated or corrupted `.spool` file by scanning
+    /// forward from the magic bytes and re-deriving the index frame by
+    /// frame, ignoring whatever (possibly unreadable) index is on disk.
+    ///
+    /// `dataspool_rs`'s on-disk index isn't public from this crate, so this
+    /// walks the same frame shape `SpoolBuilder` writes - the `SP01` magic,
+    /// then one length-prefixed card per frame - directly off the raw
+    /// bytes, stopping at the first frame whose declared length would run
+    /// past EOF or fails the sanity bound. Everything recovered up to that
+    /// point is rebuilt into a fresh, well-formed spool so the returned
+    /// archive opens and reads normally.
+    pub fn recover(path: &Path) -> Result<(Self, SalvageReport)> {
+        let raw = std::fs::read(path)
+            .with_context(|| format!("Failed to read DataSpool archive: {}", path.display()))?;
+
+        if raw.len() < SPOOL_MAGIC.len() || raw[..SPOOL_MAGIC.len()] != *SPOOL_MAGIC {
+            anyhow::bail!(
+                "Not a DataSpool archive: missing '{}' magic",
+                String::from_utf8_lossy(SPOOL_MAGIC)
+            );
+        }
+
+        let mut cursor = SPOOL_MAGIC.len();
+        let mut recovered: Vec<Vec<u8>> = Vec::new();
+        let mut corruption_offset = None;
+
+        while cursor < raw.len() {
+            if cursor + FRAME_HEADER_LEN > raw.len() {
+                corruption_offset = Some(cursor as u64);
+                break;
+            }
+
+            let mut len_bytes = [0u8; FRAME_HEADER_LEN];
+            len_bytes.copy_from_slice(&raw[cursor..cursor + FRAME_HEADER_LEN]);
+            let frame_len = u32::from_le_bytes(len_bytes) as u64;
+
+            if frame_len > MAX_SANE_FRAME_LEN {
+                corruption_offset = Some(cursor as u64);
+                break;
+            }
+
+            let data_start = cursor + FRAME_HEADER_LEN;
+            let data_end = data_start + frame_len as usize;
+            if data_end > raw.len() {
+                corruption_offset = Some(cursor as u64);
+                break;
+            }
+
+            recovered.push(raw[data_start..data_end].to_vec());
+            cursor = data_end;
+        }
+
+        let bytes_parsed = cursor as u64;
+        let recovered_cards = recovered.len();
+
+        let temp_path = path.with_extension("spool.recovered-tmp");
+        let mut builder =
+            SpoolBuilder::new(&temp_path).context("Failed to create recovery spool builder")?;
+        for card in &recovered {
+            builder.add_card(card)?;
+        }
+        builder.finalize()?;
+
+        write_checksum_sidecar(&temp_path, &recovered)?;
+
+        let reader = SpoolReader::open(&temp_path)
+            .with_context(|| format!("Failed to open recovered spool: {}", temp_path.display()))?;
+
+        let archive = Self {
+            reader: SpoolBackend::Single(reader),
+            path: temp_path,
+            layer_flags: None,
+            layer_temp_path: None,
+        };
+
+        Ok((
+            archive,
+            SalvageReport {
+                recovered_cards,
+                bytes_parsed,
+                corruption_offset,
+            },
+        ))
+    }
+}
+
 impl MutableArchive for DataSpoolArchive {
     fn write_file(&mut self, _path: &str, data: &[u8]) -> Result<()> {
         // DataSpool is append-only, so we need to reopen as builder
         // This is a limitation of the current API - we can't append in-place with SpoolReader
+        //
+        // Note: this rebuilds `self.path` as a plain (unlayered) spool, so
+        // appending to a layer-encrypted archive silently drops its
+        // compression/encryption layer. Writing through the layer stack
+        // isn't wired up yet - only `open` peels it on read.
 
         // For now, we'll create a new spool with all existing cards + new card
         let temp_path = self.path.with_extension("spool.tmp");
@@ -197,21 +733,28 @@ impl MutableArchive for DataSpoolArchive {
 
         // Copy all existing cards
         let card_count = self.reader.card_count();
+        let mut cards = Vec::with_capacity(card_count + 1);
         for index in 0..card_count {
             let card_data = self.reader.read_card(index)?;
             builder.add_card(&card_data)?;
+            cards.push(card_data);
         }
 
         // Add new card
         builder.add_card(data)?;
+        cards.push(data.to_vec());
         builder.finalize()?;
 
+        write_checksum_sidecar(&temp_path, &cards)?;
+
         // Replace original file
         std::fs::rename(&temp_path, &self.path)
             .context("Failed to replace original spool file")?;
+        std::fs::rename(checksum_sidecar_path(&temp_path), checksum_sidecar_path(&self.path))
+            .context("Failed to replace checksum sidecar")?;
 
         // Reopen reader
-        self.reader = SpoolReader::open(&self.path)?;
+        self.reader = SpoolBackend::Single(SpoolReader::open(&self.path)?);
 
         Ok(())
     }
@@ -225,3 +768,83 @@ impl MutableArchive for DataSpoolArchive {
         Ok(())
     }
 }
+
+impl DedupArchive for DataSpoolArchive {
+    /// Chunk `data` with `config`, storing each chunk this `store` hasn't
+    /// already recorded in the chunk-store spool, then append its
+    /// reconstruction recipe to the dedup sidecar - a separate virtual card
+    /// stream from `write_file`'s, addressed by [`Self::read_file_deduped`]
+    /// as `dcard_NNNNN` rather than `card_NNNNN`.
+    fn write_file_deduped(
+        &mut self,
+        _path: &str,
+        data: &[u8],
+        store: &mut ChunkStore,
+        config: ChunkerConfig,
+    ) -> Result<()> {
+        let (recipe, _newly_stored) = store.ingest_for_storage(data, config);
+        let mut sidecar = read_dedup_sidecar(&self.path).unwrap_or_default();
+
+        // Diff against the sidecar's own index rather than trusting
+        // `_newly_stored`, since a chunk `store` may carry digests from an
+        // earlier call in this session that were already flushed to disk.
+        let mut to_append = Vec::new();
+        for digest in &recipe {
+            let key = hex::encode(digest);
+            if sidecar.chunk_index.contains_key(&key) {
+                continue;
+            }
+            let bytes = store
+                .get_chunk(digest)
+                .context("Missing chunk bytes for newly ingested data")?
+                .to_vec();
+            sidecar.chunk_index.insert(key, sidecar.chunk_index.len());
+            to_append.push(bytes);
+        }
+
+        if !to_append.is_empty() {
+            append_chunks_to_store(&chunk_store_path(&self.path), &to_append)?;
+        }
+
+        sidecar.recipes.push(recipe.iter().map(hex::encode).collect());
+        write_dedup_sidecar(&self.path, &sidecar)?;
+
+        Ok(())
+    }
+
+    /// Reassemble a card previously written with `write_file_deduped` by
+    /// looking up its recipe in the dedup sidecar and concatenating each
+    /// referenced chunk, preferring bytes already held in `store` (this
+    /// session's cache) before falling back to the on-disk chunk store.
+    fn read_file_deduped(&mut self, path: &str, store: &ChunkStore) -> Result<Vec<u8>> {
+        let index = parse_card_index(path)?;
+        let sidecar = read_dedup_sidecar(&self.path)
+            .context("No dedup sidecar found; was this card written with write_file_deduped?")?;
+        let recipe = sidecar
+            .recipes
+            .get(index)
+            .with_context(|| format!("No deduped card at index {index}"))?;
+
+        let mut chunk_reader = SpoolReader::open(&chunk_store_path(&self.path))
+            .context("Failed to open chunk store")?;
+        let mut out = Vec::new();
+
+        for digest_hex in recipe {
+            if let Some(bytes) = decode_digest(digest_hex)
+                .ok()
+                .and_then(|digest| store.get_chunk(&digest))
+            {
+                out.extend_from_slice(bytes);
+                continue;
+            }
+
+            let chunk_index = *sidecar
+                .chunk_index
+                .get(digest_hex)
+                .with_context(|| format!("Unknown chunk digest {digest_hex} in recipe"))?;
+            out.extend_from_slice(&chunk_reader.read_card(chunk_index)?);
+        }
+
+        Ok(out)
+    }
+}
@@ -0,0 +1,259 @@
+//! Reader/writer for multi-volume DataSpool sets (`archive.spool.001`,
+//! `.002`, …)
+//!
+//! `dataspool_rs::SpoolReader`/`SpoolBuilder` only ever see a single file,
+//! so a split set needs its own small reader and writer rather than a thin
+//! wrapper over those types. Both walk the same frame shape
+//! [`super::dataspool::DataSpoolArchive::recover`] already assumes for a
+//! single-file spool - an `SP01` magic at the very start, then one
+//! `u32`-length-prefixed card per frame - just spread across however many
+//! part files the set has. Only the first part carries the magic; every
+//! later part is a raw continuation of the same frame sequence, so
+//! [`SplitSpoolReader`] tracks each part's size and base offset in the
+//! logical concatenated stream and seeks into whichever part a card's
+//! frame actually falls in.
+
+use anyhow::{Context, Result};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+const SPOOL_MAGIC: &[u8; 4] = b"SP01";
+const FRAME_HEADER_LEN: usize = 4;
+
+/// Split `path` into its stem and numeric suffix (e.g. `archive.spool.001`
+/// -> `("archive.spool", 1)`), or `None` if it doesn't end in a 3-digit
+/// numeric extension.
+pub(crate) fn split_suffix(path: &Path) -> Option<(String, u32)> {
+    let full = path.to_str()?;
+    let ext = path.extension()?.to_str()?;
+    if ext.len() != 3 || !ext.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let number: u32 = ext.parse().ok()?;
+    let stem_len = full.len() - ext.len() - 1;
+    Some((full[..stem_len].to_string(), number))
+}
+
+struct Part {
+    path: PathBuf,
+    /// Offset of this part's first byte in the logical concatenated stream
+    base_offset: u64,
+    size: u64,
+}
+
+struct CardLocation {
+    offset: u64,
+    length: u64,
+}
+
+/// Reads a DataSpool split across `archive.spool.001`, `.002`, … as one
+/// logical byte stream
+pub struct SplitSpoolReader {
+    parts: Vec<Part>,
+    cards: Vec<CardLocation>,
+}
+
+impl SplitSpoolReader {
+    /// Open a split set given its stem (e.g. `archive.spool` for
+    /// `archive.spool.001`, `.002`, …); discovers parts by incrementing
+    /// the numeric suffix until one is missing.
+    pub fn open(stem: &str) -> Result<Self> {
+        let mut parts = Vec::new();
+        let mut base_offset = 0u64;
+        let mut number = 1u32;
+
+        loop {
+            let part_path = PathBuf::from(format!("{stem}.{number:03}"));
+            let Ok(metadata) = std::fs::metadata(&part_path) else {
+                break;
+            };
+            let size = metadata.len();
+            parts.push(Part {
+                path: part_path,
+                base_offset,
+                size,
+            });
+            base_offset += size;
+            number += 1;
+        }
+
+        if parts.is_empty() {
+            anyhow::bail!("No DataSpool parts found for split set `{stem}`");
+        }
+
+        let mut reader = Self {
+            parts,
+            cards: Vec::new(),
+        };
+        reader.index_cards()?;
+        Ok(reader)
+    }
+
+    fn total_len(&self) -> u64 {
+        self.parts
+            .last()
+            .map(|p| p.base_offset + p.size)
+            .unwrap_or(0)
+    }
+
+    fn read_logical(&self, offset: u64, len: u64) -> Result<Vec<u8>> {
+        let mut out = Vec::with_capacity(len as usize);
+        let mut remaining = len;
+        let mut pos = offset;
+
+        while remaining > 0 {
+            let part = self
+                .parts
+                .iter()
+                .find(|p| pos >= p.base_offset && pos < p.base_offset + p.size)
+                .context("Read past the end of the split DataSpool set")?;
+
+            let local_offset = pos - part.base_offset;
+            let available = part.size - local_offset;
+            let take = remaining.min(available);
+
+            let mut file = File::open(&part.path)
+                .with_context(|| format!("Failed to open spool part: {}", part.path.display()))?;
+            file.seek(SeekFrom::Start(local_offset))?;
+            let mut buf = vec![0u8; take as usize];
+            file.read_exact(&mut buf)
+                .with_context(|| format!("Failed to read spool part: {}", part.path.display()))?;
+            out.extend_from_slice(&buf);
+
+            pos += take;
+            remaining -= take;
+        }
+
+        Ok(out)
+    }
+
+    fn index_cards(&mut self) -> Result<()> {
+        let magic = self.read_logical(0, SPOOL_MAGIC.len() as u64)?;
+        if magic != SPOOL_MAGIC {
+            anyhow::bail!("Not a DataSpool split set: missing 'SP01' magic in first part");
+        }
+
+        let total = self.total_len();
+        let mut cursor = SPOOL_MAGIC.len() as u64;
+
+        while cursor < total {
+            if cursor + FRAME_HEADER_LEN as u64 > total {
+                break;
+            }
+            let header = self.read_logical(cursor, FRAME_HEADER_LEN as u64)?;
+            let frame_len = u32::from_le_bytes(header.try_into().expect("read exactly 4 bytes")) as u64;
+            let data_start = cursor + FRAME_HEADER_LEN as u64;
+            if data_start + frame_len > total {
+                break;
+            }
+
+            self.cards.push(CardLocation {
+                offset: data_start,
+                length: frame_len,
+            });
+            cursor = data_start + frame_len;
+        }
+
+        Ok(())
+    }
+
+    pub fn card_count(&self) -> usize {
+        self.cards.len()
+    }
+
+    pub fn card_length(&self, index: usize) -> Option<u64> {
+        self.cards.get(index).map(|c| c.length)
+    }
+
+    pub fn read_card(&self, index: usize) -> Result<Vec<u8>> {
+        let loc = self
+            .cards
+            .get(index)
+            .context("Card index out of range for split DataSpool set")?;
+        self.read_logical(loc.offset, loc.length)
+    }
+}
+
+/// How large each part file is allowed to grow before
+/// [`SplitSpoolWriter`] rolls to a new one
+#[derive(Debug, Clone, Copy)]
+pub struct SplitWriterOptions {
+    pub max_part_size: u64,
+}
+
+impl Default for SplitWriterOptions {
+    fn default() -> Self {
+        Self {
+            // 1 GiB per part, a reasonable default for "awkward to move
+            // around as a single file" without the caller specifying one
+            max_part_size: 1024 * 1024 * 1024,
+        }
+    }
+}
+
+/// Writes a DataSpool split across `<stem>.001`, `.002`, …, rolling to a
+/// new part once `options.max_part_size` would be exceeded
+pub struct SplitSpoolWriter {
+    stem: String,
+    options: SplitWriterOptions,
+    part_number: u32,
+    current: File,
+    current_size: u64,
+}
+
+impl SplitSpoolWriter {
+    pub fn create(stem: &str, options: SplitWriterOptions) -> Result<Self> {
+        let part_number = 1;
+        let path = format!("{stem}.{part_number:03}");
+        let mut file =
+            File::create(&path).with_context(|| format!("Failed to create spool part: {path}"))?;
+        file.write_all(SPOOL_MAGIC)
+            .with_context(|| format!("Failed to write spool magic: {path}"))?;
+
+        Ok(Self {
+            stem: stem.to_string(),
+            options,
+            part_number,
+            current: file,
+            current_size: SPOOL_MAGIC.len() as u64,
+        })
+    }
+
+    /// Append one card, rolling to a new part first if it wouldn't fit
+    /// under `max_part_size`
+    pub fn add_card(&mut self, data: &[u8]) -> Result<()> {
+        let frame_len = FRAME_HEADER_LEN as u64 + data.len() as u64;
+        let would_exceed = self.current_size + frame_len > self.options.max_part_size;
+        let has_content = self.current_size > SPOOL_MAGIC.len() as u64;
+
+        if would_exceed && has_content {
+            self.roll_part()?;
+        }
+
+        self.current
+            .write_all(&(data.len() as u32).to_le_bytes())
+            .context("Failed to write card frame header")?;
+        self.current
+            .write_all(data)
+            .context("Failed to write card frame payload")?;
+        self.current_size += frame_len;
+
+        Ok(())
+    }
+
+    fn roll_part(&mut self) -> Result<()> {
+        self.part_number += 1;
+        let path = format!("{}.{:03}", self.stem, self.part_number);
+        self.current =
+            File::create(&path).with_context(|| format!("Failed to create spool part: {path}"))?;
+        self.current_size = 0;
+        Ok(())
+    }
+
+    pub fn finalize(mut self) -> Result<()> {
+        self.current
+            .flush()
+            .context("Failed to flush final spool part")
+    }
+}
@@ -3,6 +3,8 @@ use std::fs::File;
 use std::io::Read;
 use std::path::Path;
 
+use super::dataspool_split::split_suffix;
+
 /// Archive format types supported by Strategos
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ArchiveFormat {
@@ -12,8 +14,14 @@ pub enum ArchiveFormat {
     Cartridge,
     /// DataSpool (.spool) - Append-only item collections
     DataSpool,
+    /// DataSpool split across numbered parts (.spool.001, .002, …)
+    DataSpoolSplit,
     /// DataCard (.card) - Compressed CML documents
     DataCard,
+    /// Tar (.tar) - POSIX tar archive, interop only
+    Tar,
+    /// Zip (.zip) - PKZIP archive, interop only
+    Zip,
     /// Unknown or unsupported format
     Unknown,
 }
@@ -25,7 +33,10 @@ impl ArchiveFormat {
             ArchiveFormat::Engram => ".eng",
             ArchiveFormat::Cartridge => ".cart",
             ArchiveFormat::DataSpool => ".spool",
+            ArchiveFormat::DataSpoolSplit => ".spool.001",
             ArchiveFormat::DataCard => ".card",
+            ArchiveFormat::Tar => ".tar",
+            ArchiveFormat::Zip => ".zip",
             ArchiveFormat::Unknown => "",
         }
     }
@@ -36,7 +47,10 @@ impl ArchiveFormat {
             ArchiveFormat::Engram => "Engram",
             ArchiveFormat::Cartridge => "Cartridge",
             ArchiveFormat::DataSpool => "DataSpool",
+            ArchiveFormat::DataSpoolSplit => "DataSpool (split)",
             ArchiveFormat::DataCard => "DataCard",
+            ArchiveFormat::Tar => "Tar",
+            ArchiveFormat::Zip => "Zip",
             ArchiveFormat::Unknown => "Unknown",
         }
     }
@@ -47,6 +61,35 @@ const ENGRAM_MAGIC: &[u8] = b"\x89ENG\r\n\x1a\n"; // PNG-style
 const CARTRIDGE_MAGIC: &[u8] = b"CART\x00\x01\x00\x00";
 const DATASPOOL_MAGIC: &[u8] = b"SP01";
 const DATACARD_MAGIC: &[u8] = b"CARD";
+const ZIP_MAGIC: &[u8] = b"PK\x03\x04";
+const TAR_USTAR_MAGIC: &[u8] = b"ustar";
+const TAR_USTAR_OFFSET: u64 = 257;
+
+/// Whether `path` is part of a split DataSpool set: either it is itself a
+/// numbered part (`archive.spool.001`) whose first part carries the
+/// `SP01` magic, or it is the unsuffixed `archive.spool` name and a
+/// sibling `archive.spool.000` index file marks the set as split.
+pub(crate) fn is_split_dataspool(path: &Path) -> bool {
+    if let Some((stem, _number)) = split_suffix(path) {
+        if stem.ends_with(".spool") {
+            let first_part = format!("{stem}.001");
+            if let Ok(mut file) = File::open(&first_part) {
+                let mut header = [0u8; 4];
+                if file.read_exact(&mut header).is_ok() && header == *DATASPOOL_MAGIC {
+                    return true;
+                }
+            }
+        }
+        return false;
+    }
+
+    if path.extension().and_then(|e| e.to_str()) == Some("spool") {
+        let index_path = format!("{}.000", path.display());
+        return Path::new(&index_path).exists();
+    }
+
+    false
+}
 
 /// Detect archive format from file header
 ///
@@ -54,6 +97,10 @@ const DATACARD_MAGIC: &[u8] = b"CARD";
 /// to determine the format. Falls back to extension-based detection if magic
 /// bytes don't match any known format.
 pub fn detect_format(path: &Path) -> Result<ArchiveFormat> {
+    if is_split_dataspool(path) {
+        return Ok(ArchiveFormat::DataSpoolSplit);
+    }
+
     // First try header-based detection
     let mut file = File::open(path)
         .with_context(|| format!("Failed to open file: {}", path.display()))?;
@@ -77,24 +124,62 @@ pub fn detect_format(path: &Path) -> Result<ArchiveFormat> {
             if &header[0..4] == DATACARD_MAGIC {
                 return Ok(ArchiveFormat::DataCard);
             }
+
+            if &header[0..4] == ZIP_MAGIC {
+                return Ok(ArchiveFormat::Zip);
+            }
         }
         _ => {}
     }
 
+    // tar has no leading magic; the "ustar" tag sits 257 bytes into the header
+    if is_ustar_tar(&mut file) {
+        return Ok(ArchiveFormat::Tar);
+    }
+
     // Fall back to extension-based detection
-    if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
-        match ext.to_lowercase().as_str() {
-            "eng" => return Ok(ArchiveFormat::Engram),
-            "cart" => return Ok(ArchiveFormat::Cartridge),
-            "spool" => return Ok(ArchiveFormat::DataSpool),
-            "card" => return Ok(ArchiveFormat::DataCard),
-            _ => {}
-        }
+    let by_ext = detect_format_from_extension(path);
+    if by_ext != ArchiveFormat::Unknown {
+        return Ok(by_ext);
     }
 
     Ok(ArchiveFormat::Unknown)
 }
 
+/// Detect format purely from `path`'s extension, without touching the
+/// filesystem at all.
+///
+/// `detect_format` needs to open `path` to sniff magic bytes, which fails
+/// for a file that doesn't exist yet - exactly the case for `convert`'s
+/// destination path, which is only ever written to, never read from, to
+/// determine what to create.
+pub fn detect_format_from_extension(path: &Path) -> ArchiveFormat {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some(ext) => match ext.to_lowercase().as_str() {
+            "eng" => ArchiveFormat::Engram,
+            "cart" => ArchiveFormat::Cartridge,
+            "spool" => ArchiveFormat::DataSpool,
+            "card" => ArchiveFormat::DataCard,
+            "tar" => ArchiveFormat::Tar,
+            "zip" => ArchiveFormat::Zip,
+            _ => ArchiveFormat::Unknown,
+        },
+        None => ArchiveFormat::Unknown,
+    }
+}
+
+/// Check whether `file` carries the POSIX ustar magic at byte offset 257
+fn is_ustar_tar(file: &mut File) -> bool {
+    use std::io::{Seek, SeekFrom};
+
+    let mut tag = [0u8; 5];
+    file.seek(SeekFrom::Start(TAR_USTAR_OFFSET))
+        .ok()
+        .and_then(|_| file.read_exact(&mut tag).ok())
+        .is_some()
+        && tag == *TAR_USTAR_MAGIC
+}
+
 /// Detect format from raw bytes (useful for tests)
 pub fn detect_format_from_bytes(bytes: &[u8]) -> ArchiveFormat {
     if bytes.len() >= 8 && &bytes[0..8] == ENGRAM_MAGIC {
@@ -113,6 +198,16 @@ pub fn detect_format_from_bytes(bytes: &[u8]) -> ArchiveFormat {
         return ArchiveFormat::DataCard;
     }
 
+    if bytes.len() >= 4 && &bytes[0..4] == ZIP_MAGIC {
+        return ArchiveFormat::Zip;
+    }
+
+    if bytes.len() >= TAR_USTAR_OFFSET as usize + 5
+        && &bytes[TAR_USTAR_OFFSET as usize..TAR_USTAR_OFFSET as usize + 5] == TAR_USTAR_MAGIC
+    {
+        return ArchiveFormat::Tar;
+    }
+
     ArchiveFormat::Unknown
 }
 
@@ -144,6 +239,19 @@ mod tests {
         assert_eq!(detect_format_from_bytes(header), ArchiveFormat::DataCard);
     }
 
+    #[test]
+    fn test_detect_zip_from_bytes() {
+        let header = b"PK\x03\x04";
+        assert_eq!(detect_format_from_bytes(header), ArchiveFormat::Zip);
+    }
+
+    #[test]
+    fn test_detect_tar_from_bytes() {
+        let mut header = vec![0u8; 262];
+        header[257..262].copy_from_slice(b"ustar");
+        assert_eq!(detect_format_from_bytes(&header), ArchiveFormat::Tar);
+    }
+
     #[test]
     fn test_detect_unknown_from_bytes() {
         let header = b"UNKN";
@@ -155,7 +263,10 @@ mod tests {
         assert_eq!(ArchiveFormat::Engram.extension(), ".eng");
         assert_eq!(ArchiveFormat::Cartridge.extension(), ".cart");
         assert_eq!(ArchiveFormat::DataSpool.extension(), ".spool");
+        assert_eq!(ArchiveFormat::DataSpoolSplit.extension(), ".spool.001");
         assert_eq!(ArchiveFormat::DataCard.extension(), ".card");
+        assert_eq!(ArchiveFormat::Tar.extension(), ".tar");
+        assert_eq!(ArchiveFormat::Zip.extension(), ".zip");
     }
 
     #[test]
@@ -163,6 +274,9 @@ mod tests {
         assert_eq!(ArchiveFormat::Engram.name(), "Engram");
         assert_eq!(ArchiveFormat::Cartridge.name(), "Cartridge");
         assert_eq!(ArchiveFormat::DataSpool.name(), "DataSpool");
+        assert_eq!(ArchiveFormat::DataSpoolSplit.name(), "DataSpool (split)");
         assert_eq!(ArchiveFormat::DataCard.name(), "DataCard");
+        assert_eq!(ArchiveFormat::Tar.name(), "Tar");
+        assert_eq!(ArchiveFormat::Zip.name(), "Zip");
     }
 }
@@ -0,0 +1,32 @@
+//! Dictionary resolution for BytePunch-compressed DataCard documents
+//!
+//! `DataCardCompress`/`DataCardDecompress` take an explicit `--dict` path
+//! because the caller already knows which dictionary they used to produce
+//! the card. Searching a `.card` file is different: `engram search` only
+//! has the archive, and the dictionary it needs is whatever `dict_version`
+//! the card's metadata recorded at compression time. [`resolve`] maps that
+//! version to a file via the `STRATEGOS_DICT_DIR` environment variable,
+//! the same way [`crate::crypto::password`] resolves a password from an
+//! environment variable instead of requiring it on every command line.
+
+use anyhow::{Context, Result};
+use bytepunch_rs::Dictionary;
+
+/// Directory searched for `<dict_version>.json` dictionary files
+pub const DICT_DIR_ENV_VAR: &str = "STRATEGOS_DICT_DIR";
+
+/// Resolve the BytePunch dictionary for `dict_version` by reading
+/// `$STRATEGOS_DICT_DIR/<dict_version>.json`
+pub fn resolve(dict_version: &str) -> Result<Dictionary> {
+    let dir = std::env::var(DICT_DIR_ENV_VAR).with_context(|| {
+        format!(
+            "Dictionary version '{dict_version}' is required but {DICT_DIR_ENV_VAR} is not set"
+        )
+    })?;
+
+    let path = std::path::Path::new(&dir).join(format!("{dict_version}.json"));
+    let dict_json = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read dictionary: {}", path.display()))?;
+
+    Dictionary::from_json(&dict_json).context("Failed to parse BytePunch dictionary")
+}
@@ -1,19 +1,116 @@
 use anyhow::{Context, Result};
 use engram_rs::{ArchiveReader, VfsReader};
 use rusqlite::Connection;
+use std::collections::BTreeMap;
+use std::io::Write;
 use std::path::Path;
 
-use super::traits::{Archive, ArchiveInfo, FileEntry, OutputFormat, QueryableArchive, SearchResult};
+use super::traits::{Archive, ArchiveInfo, FileEntry, FileKind, OutputFormat, QueryableArchive, SearchResult};
+use super::unix_meta::{self, FsEntryMetadata, FS_METADATA_FILE};
+
+/// Sidecar written by `pack --dedup` (see
+/// [`crate::commands::pack::pack_files_deduped`]) mapping each original
+/// archive path to the ordered list of hex-encoded chunk digests that
+/// reconstruct it. The chunks themselves live as ordinary archive entries
+/// under `chunks/<digest>`, but neither they nor the manifest are meant to
+/// be seen as files in their own right - every read path below resolves a
+/// deduped path back into real bytes instead of handing back the manifest
+/// or a lone chunk.
+const DEDUP_MANIFEST_FILE: &str = "_dedup_manifest.json";
+
+type DedupManifest = BTreeMap<String, Vec<String>>;
+
+/// Whether `path` is part of the dedup storage machinery rather than a file
+/// the user actually packed.
+fn is_dedup_internal(path: &str) -> bool {
+    path == DEDUP_MANIFEST_FILE || path.starts_with("chunks/")
+}
 
 /// Wrapper for Engram archives (.eng)
 ///
 /// Engram archives are immutable, cryptographically signed archives
 /// designed for long-term knowledge preservation.
+///
+/// Central-directory parsing (eager vs. lazy/mmap'd, per-entry flag
+/// layout, etc.) is entirely internal to [`ArchiveReader`], which lives in
+/// the `engram_rs` crate, not here - `EngramArchive` only calls its public
+/// `open`/`read_file`/`list_files`/`get_entry` API. A reader-side change
+/// like lazily parsing entries on demand would need to land upstream in
+/// `engram_rs`; there's nothing in this crate to change for it.
 pub struct EngramArchive {
     reader: ArchiveReader,
     path: std::path::PathBuf,
 }
 
+/// Result of cross-checking one manifest signature against a resolved,
+/// externally-trusted key, returned by [`EngramArchive::verify_signers`]
+#[derive(Debug, Clone)]
+pub struct SignerVerification {
+    pub signer: Option<String>,
+    /// Whether the signature checks out against the key embedded alongside
+    /// it in the manifest (what `verify()` alone already tells you)
+    pub manifest_valid: bool,
+    /// The key the resolver says this signer should have, and where it
+    /// came from, if resolution succeeded
+    pub resolution: Option<crate::crypto::resolver::ResolvedKey>,
+    /// Whether the resolved key matches the one embedded in the manifest;
+    /// `None` if resolution failed or the signature has no signer key-id
+    pub resolved_key_matches_embedded: Option<bool>,
+}
+
+impl EngramArchive {
+    /// Read back `_dedup_manifest.json`, if `pack --dedup` wrote one.
+    fn dedup_manifest(&mut self) -> Option<DedupManifest> {
+        self.reader
+            .read_file(DEDUP_MANIFEST_FILE)
+            .ok()
+            .and_then(|data| serde_json::from_slice(&data).ok())
+    }
+
+    /// What the archive's content looks like to callers: `chunks/*` and the
+    /// manifest itself are internal storage, not files, so they're dropped
+    /// in favor of the original paths `manifest` records them under.
+    fn logical_file_paths(&mut self, manifest: &Option<DedupManifest>) -> Vec<String> {
+        let mut paths: Vec<String> = self
+            .reader
+            .list_files()
+            .iter()
+            .filter(|p| !is_dedup_internal(p))
+            .cloned()
+            .collect();
+
+        if let Some(manifest) = manifest {
+            for path in manifest.keys() {
+                if !paths.contains(path) {
+                    paths.push(path.clone());
+                }
+            }
+        }
+
+        paths
+    }
+
+    /// Read `path`'s content, transparently reassembling it from its
+    /// `chunks/<digest>` entries in order if `manifest` packed it deduped.
+    fn read_logical_file(&mut self, path: &str, manifest: &Option<DedupManifest>) -> Result<Vec<u8>> {
+        if let Some(recipe) = manifest.as_ref().and_then(|m| m.get(path)) {
+            let mut data = Vec::new();
+            for digest in recipe {
+                let chunk_path = format!("chunks/{digest}");
+                let chunk = self.reader.read_file(&chunk_path).with_context(|| {
+                    format!("Missing dedup chunk `{}` referenced by `{}`", digest, path)
+                })?;
+                data.extend_from_slice(&chunk);
+            }
+            return Ok(data);
+        }
+
+        self.reader
+            .read_file(path)
+            .with_context(|| format!("Failed to read file '{}' from Engram archive", path))
+    }
+}
+
 impl Archive for EngramArchive {
     fn open(path: &Path) -> Result<Self> {
         let reader = ArchiveReader::open(path)
@@ -26,14 +123,15 @@ impl Archive for EngramArchive {
     }
 
     fn info(&mut self) -> Result<ArchiveInfo> {
-        let files = self.reader.list_files().to_vec();
+        let manifest = self.dedup_manifest();
+        let files = self.logical_file_paths(&manifest);
         let entry_count = files.len();
 
         let mut total_size = 0u64;
         let compressed_size = 0u64; // Engram doesn't expose compressed size easily
 
         for file in &files {
-            if let Ok(data) = self.reader.read_file(file) {
+            if let Ok(data) = self.read_logical_file(file, &manifest) {
                 total_size += data.len() as u64;
                 // Engram stores uncompressed data in memory, so compressed_size
                 // is approximated from the archive file
@@ -66,12 +164,13 @@ impl Archive for EngramArchive {
     }
 
     fn list_files(&mut self) -> Result<Vec<FileEntry>> {
-        let files = self.reader.list_files().to_vec();
+        let manifest = self.dedup_manifest();
+        let files = self.logical_file_paths(&manifest);
         let mut entries = Vec::new();
 
         for file_path in files {
             // Engram doesn't expose per-file metadata easily, so we approximate
-            if let Ok(data) = self.reader.read_file(&file_path) {
+            if let Ok(data) = self.read_logical_file(&file_path, &manifest) {
                 entries.push(FileEntry {
                     path: file_path,
                     size: data.len() as u64,
@@ -79,6 +178,8 @@ impl Archive for EngramArchive {
                     compression_method: "unknown".to_string(),
                     modified: None,
                     crc32: None,
+                    // Engram stores plain file bytes with no POSIX metadata
+                    ..Default::default()
                 });
             }
         }
@@ -87,29 +188,111 @@ impl Archive for EngramArchive {
     }
 
     fn read_file(&mut self, path: &str) -> Result<Vec<u8>> {
-        self.reader
-            .read_file(path)
-            .with_context(|| format!("Failed to read file '{}' from Engram archive", path))
+        let manifest = self.dedup_manifest();
+        self.read_logical_file(path, &manifest)
     }
 
-    fn extract(&mut self, output: &Path, files: Option<&[String]>) -> Result<()> {
-        let files_to_extract = match files {
+    fn extract(
+        &mut self,
+        output: &Path,
+        files: Option<&[String]>,
+        limits: crate::extract::ExtractLimits,
+    ) -> Result<()> {
+        // `_fs_metadata.json`, when present, was written by `pack
+        // --preserve-metadata` (see `crate::commands::pack`) and describes
+        // every directory, symlink, and device/fifo node in the tree, none
+        // of which have content stored as a regular archive file
+        let fs_metadata: BTreeMap<String, FsEntryMetadata> = self
+            .reader
+            .read_file(FS_METADATA_FILE)
+            .ok()
+            .and_then(|data| serde_json::from_slice(&data).ok())
+            .unwrap_or_default();
+
+        // `_dedup_manifest.json`, when present, was written by `pack --dedup`
+        // and maps each original path to the `chunks/<digest>` entries that
+        // reconstruct it - neither the manifest nor the chunks are files in
+        // their own right, so `logical_file_paths` folds them back into the
+        // paths `manifest` records instead of extracting them verbatim
+        let manifest = self.dedup_manifest();
+
+        let files_to_extract: Vec<String> = match files {
             Some(f) => f.to_vec(),
-            None => self.reader.list_files().to_vec(),
+            None => {
+                let mut paths: Vec<String> = self
+                    .logical_file_paths(&manifest)
+                    .into_iter()
+                    .filter(|p| p.as_str() != FS_METADATA_FILE)
+                    .collect();
+                // Directories/symlinks/devices/fifos have no archive content,
+                // so they only show up in the sidecar, not `list_files`
+                for path in fs_metadata.keys() {
+                    if !paths.contains(path) {
+                        paths.push(path.clone());
+                    }
+                }
+                paths.sort();
+                paths
+            }
         };
 
+        let mut guard = crate::extract::ExtractGuard::new(output, limits)?;
+
         for file_path in files_to_extract {
-            let data = self.read_file(&file_path)?;
-            let output_path = output.join(&file_path);
+            let entry_meta = fs_metadata.get(&file_path);
+            let kind = entry_meta.map(|m| FileKind::from(&m.kind)).unwrap_or(FileKind::Regular);
+
+            let output_path = guard.resolve(&file_path)?;
 
-            // Create parent directories
-            if let Some(parent) = output_path.parent() {
-                std::fs::create_dir_all(parent)
-                    .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+            match &kind {
+                FileKind::Directory => {
+                    guard.charge(0)?;
+                    std::fs::create_dir_all(&output_path)?;
+                    guard.charge_written(0)?;
+                }
+                FileKind::Symlink { target } => {
+                    guard.charge(0)?;
+                    guard.validate_symlink_target(&output_path, target)?;
+                    let _ = std::fs::remove_file(&output_path);
+                    std::os::unix::fs::symlink(target, &output_path).with_context(|| {
+                        format!("Failed to create symlink: {}", output_path.display())
+                    })?;
+                    guard.charge_written(0)?;
+                }
+                FileKind::CharDevice { major, minor } | FileKind::BlockDevice { major, minor } => {
+                    guard.charge(0)?;
+                    unix_meta::mknod(&output_path, &kind, *major, *minor)?;
+                    guard.charge_written(0)?;
+                }
+                FileKind::Fifo => {
+                    guard.charge(0)?;
+                    unix_meta::mkfifo(&output_path)?;
+                    guard.charge_written(0)?;
+                }
+                FileKind::Regular => {
+                    let data = self.read_logical_file(&file_path, &manifest)?;
+                    guard.charge(data.len() as u64)?;
+
+                    let mut out_file = std::fs::File::create(&output_path)
+                        .with_context(|| format!("Failed to create file: {}", output_path.display()))?;
+                    out_file
+                        .write_all(&data)
+                        .with_context(|| format!("Failed to write file: {}", output_path.display()))?;
+                    guard.charge_written(data.len() as u64)?;
+                }
             }
 
-            std::fs::write(&output_path, data)
-                .with_context(|| format!("Failed to write file: {}", output_path.display()))?;
+            if let Some(entry_meta) = entry_meta {
+                let xattrs = unix_meta::decode_xattrs(&entry_meta.xattrs);
+                unix_meta::apply(
+                    &output_path,
+                    entry_meta.mode,
+                    entry_meta.uid,
+                    entry_meta.gid,
+                    &xattrs,
+                    &kind,
+                )?;
+            }
         }
 
         Ok(())
@@ -133,12 +316,65 @@ impl Archive for EngramArchive {
         Ok(true)
     }
 
+    /// Verify every manifest signature and, for each, try to resolve its
+    /// claimed signer key-id through `resolver` so the caller can tell
+    /// whether the key embedded in the manifest actually matches the key a
+    /// trusted source (keyserver or local directory) says that signer
+    /// should have. A signature can check out internally against its own
+    /// embedded key while still failing this cross-check, which is the
+    /// case that matters for archives received from a third party.
+    pub fn verify_signers(
+        &mut self,
+        resolver: &dyn crate::crypto::resolver::KeyResolver,
+    ) -> Result<Vec<SignerVerification>> {
+        let Some(manifest_value) = self.reader.read_manifest()? else {
+            return Ok(Vec::new());
+        };
+        let manifest: engram_rs::Manifest = serde_json::from_value(manifest_value)
+            .context("Failed to parse Engram manifest")?;
+
+        let manifest_checks = manifest.verify_signatures().unwrap_or_default();
+
+        let mut results = Vec::new();
+        for (i, sig) in manifest.signatures.iter().enumerate() {
+            let manifest_valid = manifest_checks.get(i).copied().unwrap_or(false);
+
+            let resolution = sig
+                .signer
+                .as_deref()
+                .map(|key_id| resolver.resolve(key_id));
+
+            let resolved_key_matches_embedded = match &resolution {
+                Some(Ok(resolved)) => {
+                    ed25519_dalek::VerifyingKey::from_bytes(&sig.public_key)
+                        .ok()
+                        .map(|embedded| crate::crypto::resolver::keys_match(&resolved.key, &embedded))
+                }
+                _ => None,
+            };
+
+            results.push(SignerVerification {
+                signer: sig.signer.clone(),
+                manifest_valid,
+                resolution: resolution.and_then(|r| r.ok()),
+                resolved_key_matches_embedded,
+            });
+        }
+
+        Ok(results)
+    }
+
     fn search(&mut self, pattern: &str, case_insensitive: bool) -> Result<Vec<SearchResult>> {
         let mut results = Vec::new();
-        let files = self.reader.list_files().to_vec();
+        let manifest = self.dedup_manifest();
+        let files = self.logical_file_paths(&manifest);
+        let options = crate::search::SearchOptions {
+            case_insensitive,
+            ..Default::default()
+        };
 
         for file_path in files {
-            let data = match self.reader.read_file(&file_path) {
+            let data = match self.read_logical_file(&file_path, &manifest) {
                 Ok(d) => d,
                 Err(_) => continue,
             };
@@ -148,29 +384,15 @@ impl Archive for EngramArchive {
                 Err(_) => continue, // Skip binary files
             };
 
-            for (line_number, line) in content.lines().enumerate() {
-                let matches = if case_insensitive {
-                    line.to_lowercase().contains(&pattern.to_lowercase())
-                } else {
-                    line.contains(pattern)
-                };
-
-                if matches {
-                    let match_offset = if case_insensitive {
-                        line.to_lowercase()
-                            .find(&pattern.to_lowercase())
-                            .unwrap_or(0)
-                    } else {
-                        line.find(pattern).unwrap_or(0)
-                    };
-
-                    results.push(SearchResult {
-                        file_path: file_path.clone(),
-                        line_number: line_number + 1,
-                        line_content: line.to_string(),
-                        match_offset,
-                    });
-                }
+            for m in crate::search::find_matches(&content, pattern, &options)? {
+                results.push(SearchResult {
+                    file_path: file_path.clone(),
+                    line_number: m.line_number,
+                    line_content: m.line_content,
+                    match_spans: m.match_spans,
+                    before: m.before.clone(),
+                    after: m.after.clone(),
+                });
             }
         }
 
@@ -187,9 +409,7 @@ impl QueryableArchive for EngramArchive {
         let all_files = self.reader.list_files().to_vec();
         Ok(all_files
             .into_iter()
-            .filter(|f| {
-                f.ends_with(".db") || f.ends_with(".sqlite") || f.ends_with(".sqlite3")
-            })
+            .filter(|f| super::traits::is_database_path(f))
             .collect())
     }
 
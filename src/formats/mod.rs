@@ -3,14 +3,31 @@ pub mod detection;
 pub mod traits;
 
 pub mod engram;
+#[cfg(feature = "async")]
+pub mod async_engram;
 pub mod cartridge;
 pub mod dataspool;
+pub mod dataspool_split;
+pub mod layered;
 pub mod datacard;
+pub mod tar_archive;
+pub mod zip_archive;
+pub(crate) mod dictionary;
+pub(crate) mod unix_meta;
 
 // Re-export main types
-pub use detection::{ArchiveFormat, detect_format};
-pub use traits::{Archive, MutableArchive, QueryableArchive, ArchiveInfo, FileEntry, SearchResult};
+pub use detection::{detect_format, detect_format_from_extension, ArchiveFormat};
+pub use traits::{
+    is_database_path, Archive, ArchiveInfo, FileEntry, FileKind, MutableArchive, QueryableArchive,
+    SearchResult,
+};
 pub use engram::EngramArchive;
+#[cfg(feature = "async")]
+pub use async_engram::{extract_async, AsyncArchiveReader, AsyncEntry};
 pub use cartridge::CartridgeArchive;
 pub use dataspool::DataSpoolArchive;
+pub use dataspool_split::{SplitSpoolReader, SplitSpoolWriter, SplitWriterOptions};
+pub use layered::{LayerFlags, LayeredReader, LayeredWriter};
 pub use datacard::DataCardArchive;
+pub use tar_archive::TarArchive;
+pub use zip_archive::ZipArchive;
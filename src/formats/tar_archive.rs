@@ -0,0 +1,246 @@
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::path::Path;
+
+use super::traits::{Archive, ArchiveInfo, FileEntry, FileKind, SearchResult};
+
+/// Wrapper for plain POSIX tar archives (.tar)
+///
+/// Tar is not a native Strategos format; this wrapper exists purely for
+/// interop so archives can be migrated into/out of the native formats via
+/// `convert`. Tar has no central directory, so each operation re-reads the
+/// file from the start. Unlike the native formats, tar headers natively
+/// carry Unix mode/ownership and node type (symlink, device, fifo), which
+/// we surface through `FileEntry` so a round trip through `extract`
+/// restores the original tree faithfully.
+pub struct TarArchive {
+    path: std::path::PathBuf,
+}
+
+impl TarArchive {
+    fn reader(&self) -> Result<tar::Archive<File>> {
+        let file = File::open(&self.path)
+            .with_context(|| format!("Failed to open tar archive: {}", self.path.display()))?;
+        Ok(tar::Archive::new(file))
+    }
+}
+
+/// Read the metadata tar carries for `entry` into a `FileEntry` template
+///
+/// `size`/`compressed_size`/`crc32` are left for the caller to fill in, since
+/// they differ between `list_files` (header size) and other callers.
+fn entry_metadata(entry: &tar::Entry<'_, File>) -> Result<(Option<u32>, Option<u32>, Option<u32>, FileKind, BTreeMap<String, Vec<u8>>)> {
+    let header = entry.header();
+    let mode = header.mode().ok();
+    let uid = header.uid().ok().map(|v| v as u32);
+    let gid = header.gid().ok().map(|v| v as u32);
+
+    let kind = match header.entry_type() {
+        tar::EntryType::Symlink => FileKind::Symlink {
+            target: entry
+                .link_name()?
+                .map(|p| p.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+        },
+        tar::EntryType::Directory => FileKind::Directory,
+        tar::EntryType::Char => FileKind::CharDevice {
+            major: header.device_major()?.unwrap_or(0),
+            minor: header.device_minor()?.unwrap_or(0),
+        },
+        tar::EntryType::Block => FileKind::BlockDevice {
+            major: header.device_major()?.unwrap_or(0),
+            minor: header.device_minor()?.unwrap_or(0),
+        },
+        tar::EntryType::Fifo => FileKind::Fifo,
+        _ => FileKind::Regular,
+    };
+
+    let mut xattrs = BTreeMap::new();
+    if let Some(extensions) = entry.pax_extensions()? {
+        for extension in extensions {
+            let extension = extension?;
+            if let Some(name) = extension.key()?.strip_prefix("SCHILY.xattr.") {
+                xattrs.insert(name.to_string(), extension.value_bytes().to_vec());
+            }
+        }
+    }
+
+    Ok((mode, uid, gid, kind, xattrs))
+}
+
+impl Archive for TarArchive {
+    fn open(path: &Path) -> Result<Self> {
+        Ok(Self {
+            path: path.to_path_buf(),
+        })
+    }
+
+    fn info(&mut self) -> Result<ArchiveInfo> {
+        let files = self.list_files()?;
+        let total_size = files.iter().map(|f| f.size).sum();
+
+        Ok(ArchiveInfo {
+            format: "Tar".to_string(),
+            version: "ustar".to_string(),
+            entry_count: files.len(),
+            total_size,
+            compressed_size: 0,
+            compression_ratio: 1.0,
+            metadata: serde_json::json!({ "format": "tar" }),
+        })
+    }
+
+    fn list_files(&mut self) -> Result<Vec<FileEntry>> {
+        let mut archive = self.reader()?;
+        let mut entries = Vec::new();
+
+        for entry in archive.entries()? {
+            let entry = entry?;
+            let path = entry.path()?.to_string_lossy().trim_end_matches('/').replace('\\', "/");
+            let size = entry.header().size().unwrap_or(0);
+            let (mode, uid, gid, kind, xattrs) = entry_metadata(&entry)?;
+
+            entries.push(FileEntry {
+                path,
+                size,
+                compressed_size: size,
+                compression_method: "none".to_string(),
+                modified: entry.header().mtime().ok(),
+                crc32: None,
+                mode,
+                uid,
+                gid,
+                kind,
+                xattrs,
+            });
+        }
+
+        Ok(entries)
+    }
+
+    fn read_file(&mut self, path: &str) -> Result<Vec<u8>> {
+        let mut archive = self.reader()?;
+
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            if entry.path()?.to_string_lossy().replace('\\', "/") == path {
+                let mut buf = Vec::new();
+                std::io::Read::read_to_end(&mut entry, &mut buf)?;
+                return Ok(buf);
+            }
+        }
+
+        anyhow::bail!("File '{}' not found in tar archive", path)
+    }
+
+    fn extract(
+        &mut self,
+        output: &Path,
+        files: Option<&[String]>,
+        limits: crate::extract::ExtractLimits,
+    ) -> Result<()> {
+        let mut archive = self.reader()?;
+        let mut guard = crate::extract::ExtractGuard::new(output, limits)?;
+
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            let path = entry.path()?.to_string_lossy().trim_end_matches('/').replace('\\', "/");
+            if path.is_empty() {
+                continue;
+            }
+            if let Some(wanted) = files {
+                if !wanted.iter().any(|f| f == &path) {
+                    continue;
+                }
+            }
+
+            let (mode, uid, gid, kind, xattrs) = entry_metadata(&entry)?;
+            let size = entry.header().size().unwrap_or(0);
+            guard.charge(size)?;
+            let output_path = guard.resolve(&path)?;
+
+            match &kind {
+                FileKind::Directory => {
+                    std::fs::create_dir_all(&output_path)?;
+                }
+                FileKind::Symlink { target } => {
+                    guard.validate_symlink_target(&output_path, target)?;
+                    let _ = std::fs::remove_file(&output_path);
+                    std::os::unix::fs::symlink(target, &output_path).with_context(|| {
+                        format!("Failed to create symlink: {}", output_path.display())
+                    })?;
+                }
+                FileKind::CharDevice { major, minor } | FileKind::BlockDevice { major, minor } => {
+                    super::unix_meta::mknod(&output_path, &kind, *major, *minor)?;
+                }
+                FileKind::Fifo => {
+                    super::unix_meta::mkfifo(&output_path)?;
+                }
+                FileKind::Regular => {
+                    let mut out_file = File::create(&output_path).with_context(|| {
+                        format!("Failed to create file: {}", output_path.display())
+                    })?;
+                    std::io::copy(&mut entry, &mut out_file)?;
+                }
+            }
+            guard.charge_written(if matches!(kind, FileKind::Regular) { size } else { 0 })?;
+
+            super::unix_meta::apply(&output_path, mode, uid, gid, &xattrs, &kind)?;
+        }
+
+        Ok(())
+    }
+
+    fn verify(&mut self) -> Result<bool> {
+        // Tar has no checksums of its own beyond the per-header checksum,
+        // which the `tar` crate already validates while iterating entries.
+        match self.reader().and_then(|mut a| Ok(a.entries()?.count())) {
+            Ok(_) => Ok(true),
+            Err(_) => Ok(false),
+        }
+    }
+
+    fn search(&mut self, pattern: &str, case_insensitive: bool) -> Result<Vec<SearchResult>> {
+        let mut results = Vec::new();
+        let mut archive = self.reader()?;
+        let options = crate::search::SearchOptions {
+            case_insensitive,
+            ..Default::default()
+        };
+
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            if !entry.header().entry_type().is_file() {
+                continue;
+            }
+
+            let path = entry.path()?.to_string_lossy().replace('\\', "/");
+            let mut buf = Vec::new();
+            if std::io::Read::read_to_end(&mut entry, &mut buf).is_err() {
+                continue;
+            }
+
+            let Ok(content) = String::from_utf8(buf) else {
+                continue;
+            };
+
+            for m in crate::search::find_matches(&content, pattern, &options)? {
+                results.push(SearchResult {
+                    file_path: path.clone(),
+                    line_number: m.line_number,
+                    line_content: m.line_content,
+                    match_spans: m.match_spans,
+                    before: m.before.clone(),
+                    after: m.after.clone(),
+                });
+            }
+        }
+
+        Ok(results)
+    }
+
+    fn format_name(&self) -> &'static str {
+        "Tar"
+    }
+}
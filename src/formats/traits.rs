@@ -1,5 +1,6 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::Path;
 
 /// Archive metadata and statistics
@@ -14,8 +15,31 @@ pub struct ArchiveInfo {
     pub metadata: serde_json::Value,
 }
 
+/// The POSIX node type an entry should be restored as on extract
+///
+/// Most formats only ever produce `Regular`; `Tar` and `Zip` can carry the
+/// full set when the source tree had symlinks or device/fifo nodes.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum FileKind {
+    #[default]
+    Regular,
+    Directory,
+    Symlink {
+        target: String,
+    },
+    CharDevice {
+        major: u32,
+        minor: u32,
+    },
+    BlockDevice {
+        major: u32,
+        minor: u32,
+    },
+    Fifo,
+}
+
 /// File entry metadata
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct FileEntry {
     pub path: String,
     pub size: u64,
@@ -23,15 +47,39 @@ pub struct FileEntry {
     pub compression_method: String,
     pub modified: Option<u64>,
     pub crc32: Option<u32>,
+    /// POSIX permission bits, when the source format carries them
+    pub mode: Option<u32>,
+    pub uid: Option<u32>,
+    pub gid: Option<u32>,
+    /// Node type; defaults to `Regular` for formats with no concept of one
+    pub kind: FileKind,
+    /// Extended attributes, keyed by namespaced name (e.g. `user.comment`)
+    pub xattrs: BTreeMap<String, Vec<u8>>,
+}
+
+/// Whether `path` looks like an embedded SQLite database, by extension
+///
+/// Shared by [`QueryableArchive::is_database`], each format's
+/// `list_databases`, and the archive catalog so "database" means the same
+/// thing everywhere it's checked.
+pub fn is_database_path(path: &str) -> bool {
+    path.ends_with(".db") || path.ends_with(".sqlite") || path.ends_with(".sqlite3")
 }
 
 /// Search result from pattern matching
-#[derive(Debug, Clone)]
+///
+/// `match_spans` holds the (start, end) byte range of every match on the
+/// line, not just the first, so callers can highlight or emit JSON for each.
+/// `before`/`after` carry requested context lines and are empty unless the
+/// caller asked for context (plain `Archive::search` never does).
+#[derive(Debug, Clone, Default)]
 pub struct SearchResult {
     pub file_path: String,
     pub line_number: usize,
     pub line_content: String,
-    pub match_offset: usize,
+    pub match_spans: Vec<(usize, usize)>,
+    pub before: Vec<String>,
+    pub after: Vec<String>,
 }
 
 /// Output format for query results
@@ -60,9 +108,29 @@ pub trait Archive {
     /// Read a specific file's contents
     fn read_file(&mut self, path: &str) -> Result<Vec<u8>>;
 
+    /// Stream a file's contents directly to `writer`, returning the number of
+    /// bytes written.
+    ///
+    /// The default implementation buffers the whole file via `read_file` and
+    /// writes it in one shot; formats that can decode incrementally should
+    /// override this to avoid holding multi-GB entries in memory.
+    fn read_file_to(&mut self, path: &str, writer: &mut dyn std::io::Write) -> Result<u64> {
+        let data = self.read_file(path)?;
+        writer.write_all(&data)?;
+        Ok(data.len() as u64)
+    }
+
     /// Extract files to output directory
-    /// If files is None, extract all files
-    fn extract(&mut self, output: &Path, files: Option<&[String]>) -> Result<()>;
+    ///
+    /// If `files` is `None`, extract all files. Implementations route every
+    /// entry through [`crate::extract::ExtractGuard`] so path-traversal and
+    /// the `limits` ceilings are enforced the same way for every format.
+    fn extract(
+        &mut self,
+        output: &Path,
+        files: Option<&[String]>,
+        limits: crate::extract::ExtractLimits,
+    ) -> Result<()>;
 
     /// Verify archive integrity (checksums, signatures)
     fn verify(&mut self) -> Result<bool>;
@@ -100,6 +168,6 @@ pub trait QueryableArchive: Archive {
 
     /// Check if a specific file is a SQLite database
     fn is_database(&mut self, path: &str) -> Result<bool> {
-        Ok(path.ends_with(".db") || path.ends_with(".sqlite") || path.ends_with(".sqlite3"))
+        Ok(is_database_path(path))
     }
 }
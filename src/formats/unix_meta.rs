@@ -0,0 +1,265 @@
+//! POSIX metadata helpers shared by the `tar` and `zip` interop wrappers,
+//! and by `pack`/`EngramArchive` when packing or extracting with
+//! `--preserve-metadata`
+//!
+//! Plain files written through `std::fs` only get a default mode, and Rust's
+//! standard library has no portable way to create device/fifo nodes or set
+//! extended attributes, so the handful of libc calls needed for a faithful
+//! extract live here rather than duplicated in both wrappers. `tar` and
+//! `zip` carry this metadata in their own headers; Engram has no such
+//! concept, so `pack`/`EngramArchive::extract` instead read and write it
+//! through [`FsEntryMetadata`] and the [`FS_METADATA_FILE`] sidecar.
+
+use anyhow::{Context, Result};
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::ffi::CString;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::{FileTypeExt, MetadataExt, PermissionsExt};
+use std::path::Path;
+
+use super::traits::FileKind;
+
+/// Name of the sidecar manifest `pack --preserve-metadata` writes into the
+/// archive, and that `EngramArchive::extract` consults to recreate
+/// symlinks/devices/fifos and restore mode/ownership/xattrs. Engram has no
+/// native directory or special-file entries, so every non-regular node is
+/// recorded here instead of as archive content.
+pub const FS_METADATA_FILE: &str = "_fs_metadata.json";
+
+/// One archive entry's captured POSIX metadata, as stored in
+/// [`FS_METADATA_FILE`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FsEntryMetadata {
+    pub kind: FsEntryKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uid: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gid: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mtime: Option<i64>,
+    /// Extended attribute values, base64-encoded since xattr values are
+    /// arbitrary bytes and this sidecar is plain JSON
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub xattrs: BTreeMap<String, String>,
+}
+
+/// The node type half of [`FsEntryMetadata`], mirroring [`FileKind`] in a
+/// JSON-serializable form
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FsEntryKind {
+    Regular,
+    Directory,
+    Symlink { target: String },
+    CharDevice { major: u32, minor: u32 },
+    BlockDevice { major: u32, minor: u32 },
+    Fifo,
+}
+
+impl From<&FileKind> for FsEntryKind {
+    fn from(kind: &FileKind) -> Self {
+        match kind {
+            FileKind::Regular => FsEntryKind::Regular,
+            FileKind::Directory => FsEntryKind::Directory,
+            FileKind::Symlink { target } => FsEntryKind::Symlink {
+                target: target.clone(),
+            },
+            FileKind::CharDevice { major, minor } => FsEntryKind::CharDevice {
+                major: *major,
+                minor: *minor,
+            },
+            FileKind::BlockDevice { major, minor } => FsEntryKind::BlockDevice {
+                major: *major,
+                minor: *minor,
+            },
+            FileKind::Fifo => FsEntryKind::Fifo,
+        }
+    }
+}
+
+impl From<&FsEntryKind> for FileKind {
+    fn from(kind: &FsEntryKind) -> Self {
+        match kind {
+            FsEntryKind::Regular => FileKind::Regular,
+            FsEntryKind::Directory => FileKind::Directory,
+            FsEntryKind::Symlink { target } => FileKind::Symlink {
+                target: target.clone(),
+            },
+            FsEntryKind::CharDevice { major, minor } => FileKind::CharDevice {
+                major: *major,
+                minor: *minor,
+            },
+            FsEntryKind::BlockDevice { major, minor } => FileKind::BlockDevice {
+                major: *major,
+                minor: *minor,
+            },
+            FsEntryKind::Fifo => FileKind::Fifo,
+        }
+    }
+}
+
+/// Classify a filesystem node and capture the metadata needed to recreate
+/// it faithfully: its node type, mode, ownership, mtime, and xattrs.
+///
+/// `meta` must come from `symlink_metadata` (not `metadata`), so that
+/// symlinks are classified as links rather than followed.
+pub fn capture(path: &Path, meta: &std::fs::Metadata) -> Result<FsEntryMetadata> {
+    let file_type = meta.file_type();
+    let kind = if file_type.is_symlink() {
+        let target = std::fs::read_link(path)
+            .with_context(|| format!("Failed to read symlink target for {}", path.display()))?;
+        let target = target
+            .to_str()
+            .with_context(|| format!("Symlink target for {} is not valid UTF-8", path.display()))?
+            .to_string();
+        FsEntryKind::Symlink { target }
+    } else if file_type.is_dir() {
+        FsEntryKind::Directory
+    } else if file_type.is_fifo() {
+        FsEntryKind::Fifo
+    } else if file_type.is_char_device() {
+        let (major, minor) = device_numbers(meta.rdev());
+        FsEntryKind::CharDevice { major, minor }
+    } else if file_type.is_block_device() {
+        let (major, minor) = device_numbers(meta.rdev());
+        FsEntryKind::BlockDevice { major, minor }
+    } else {
+        FsEntryKind::Regular
+    };
+
+    let mut xattrs = BTreeMap::new();
+    if let Ok(names) = xattr::list(path) {
+        for name in names {
+            let (Some(name), Ok(Some(value))) = (name.to_str(), xattr::get(path, &name)) else {
+                continue;
+            };
+            xattrs.insert(
+                name.to_string(),
+                base64::engine::general_purpose::STANDARD.encode(value),
+            );
+        }
+    }
+
+    Ok(FsEntryMetadata {
+        kind,
+        mode: Some(meta.mode()),
+        uid: Some(meta.uid()),
+        gid: Some(meta.gid()),
+        mtime: Some(meta.mtime()),
+        xattrs,
+    })
+}
+
+/// Decode [`FsEntryMetadata::xattrs`] back into raw bytes for [`apply`]
+pub fn decode_xattrs(xattrs: &BTreeMap<String, String>) -> BTreeMap<String, Vec<u8>> {
+    xattrs
+        .iter()
+        .filter_map(|(name, value)| {
+            base64::engine::general_purpose::STANDARD
+                .decode(value)
+                .ok()
+                .map(|bytes| (name.clone(), bytes))
+        })
+        .collect()
+}
+
+fn device_numbers(rdev: u64) -> (u32, u32) {
+    unsafe {
+        (
+            libc::major(rdev as libc::dev_t) as u32,
+            libc::minor(rdev as libc::dev_t) as u32,
+        )
+    }
+}
+
+fn path_to_cstring(path: &Path) -> Result<CString> {
+    CString::new(path.as_os_str().as_bytes())
+        .with_context(|| format!("Path contains a NUL byte: {}", path.display()))
+}
+
+/// Create a character or block device node at `path`
+pub fn mknod(path: &Path, kind: &FileKind, major: u32, minor: u32) -> Result<()> {
+    let mode = match kind {
+        FileKind::CharDevice { .. } => libc::S_IFCHR,
+        FileKind::BlockDevice { .. } => libc::S_IFBLK,
+        _ => anyhow::bail!("mknod called with non-device FileKind"),
+    };
+
+    let c_path = path_to_cstring(path)?;
+    let dev = unsafe { libc::makedev(major, minor) };
+    let ret = unsafe { libc::mknod(c_path.as_ptr(), mode as libc::mode_t | 0o600, dev) };
+    if ret != 0 {
+        anyhow::bail!(
+            "Failed to create device node {}: {}",
+            path.display(),
+            std::io::Error::last_os_error()
+        );
+    }
+
+    Ok(())
+}
+
+/// Create a FIFO (named pipe) at `path`
+pub fn mkfifo(path: &Path) -> Result<()> {
+    let c_path = path_to_cstring(path)?;
+    let ret = unsafe { libc::mkfifo(c_path.as_ptr(), 0o600) };
+    if ret != 0 {
+        anyhow::bail!(
+            "Failed to create fifo {}: {}",
+            path.display(),
+            std::io::Error::last_os_error()
+        );
+    }
+
+    Ok(())
+}
+
+/// Apply mode, ownership, and xattrs to a freshly-extracted node
+///
+/// Symlinks have no mode of their own on Linux (there is no `lchmod`), so
+/// that step is skipped for them; ownership and xattrs are still applied.
+pub fn apply(
+    path: &Path,
+    mode: Option<u32>,
+    uid: Option<u32>,
+    gid: Option<u32>,
+    xattrs: &BTreeMap<String, Vec<u8>>,
+    kind: &FileKind,
+) -> Result<()> {
+    let is_symlink = matches!(kind, FileKind::Symlink { .. });
+
+    if !is_symlink {
+        if let Some(mode) = mode {
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode))
+                .with_context(|| format!("Failed to set mode on {}", path.display()))?;
+        }
+    }
+
+    if uid.is_some() || gid.is_some() {
+        let c_path = path_to_cstring(path)?;
+        let uid = uid.map(|v| v as libc::uid_t).unwrap_or(u32::MAX as libc::uid_t);
+        let gid = gid.map(|v| v as libc::gid_t).unwrap_or(u32::MAX as libc::gid_t);
+        let ret = unsafe { libc::lchown(c_path.as_ptr(), uid, gid) };
+        if ret != 0 {
+            // Restoring ownership requires privileges the caller may not have;
+            // treat failure as non-fatal rather than aborting the whole extract.
+            eprintln!(
+                "Warning: failed to chown {}: {}",
+                path.display(),
+                std::io::Error::last_os_error()
+            );
+        }
+    }
+
+    for (name, value) in xattrs {
+        if xattr::set(path, name, value).is_err() {
+            eprintln!("Warning: failed to set xattr '{}' on {}", name, path.display());
+        }
+    }
+
+    Ok(())
+}
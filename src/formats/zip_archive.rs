@@ -0,0 +1,282 @@
+use anyhow::{Context, Result};
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use super::traits::{Archive, ArchiveInfo, FileEntry, FileKind, MutableArchive, SearchResult};
+
+/// Unix S_IFLNK bits as stored in the upper 16 bits of a zip external attribute
+const S_IFLNK: u32 = 0o120000;
+const S_IFMT: u32 = 0o170000;
+
+/// Classify a zip entry's node type from its Unix mode bits and directory flag
+///
+/// Symlinks are stored as regular entries whose *content* is the link
+/// target, tagged via the Unix mode bits zip borrows from the external file
+/// attributes field (the convention `tar`/`unzip` also rely on).
+fn entry_kind(is_dir: bool, unix_mode: Option<u32>, target: impl FnOnce() -> Result<String>) -> Result<FileKind> {
+    if is_dir {
+        return Ok(FileKind::Directory);
+    }
+    if let Some(mode) = unix_mode {
+        if mode & S_IFMT == S_IFLNK {
+            return Ok(FileKind::Symlink { target: target()? });
+        }
+    }
+    Ok(FileKind::Regular)
+}
+
+/// Wrapper for PKZIP archives (.zip)
+///
+/// Zip is not a native Strategos format; this wrapper exists purely for
+/// interop so archives can be migrated into/out of the native formats via
+/// `convert`. Writes are supported by appending new entries through
+/// `zip::ZipWriter::new_append`; deletion would require rewriting the whole
+/// central directory and isn't implemented.
+pub struct ZipArchive {
+    path: std::path::PathBuf,
+}
+
+impl Archive for ZipArchive {
+    fn open(path: &Path) -> Result<Self> {
+        Ok(Self {
+            path: path.to_path_buf(),
+        })
+    }
+
+    fn info(&mut self) -> Result<ArchiveInfo> {
+        let file = File::open(&self.path)
+            .with_context(|| format!("Failed to open zip archive: {}", self.path.display()))?;
+        let mut zip = zip::ZipArchive::new(file)
+            .with_context(|| format!("Failed to read zip archive: {}", self.path.display()))?;
+
+        let mut total_size = 0u64;
+        let mut compressed_size = 0u64;
+
+        for i in 0..zip.len() {
+            let entry = zip.by_index(i)?;
+            total_size += entry.size();
+            compressed_size += entry.compressed_size();
+        }
+
+        let compression_ratio = if compressed_size > 0 {
+            total_size as f64 / compressed_size as f64
+        } else {
+            1.0
+        };
+
+        Ok(ArchiveInfo {
+            format: "Zip".to_string(),
+            version: "2.0".to_string(),
+            entry_count: zip.len(),
+            total_size,
+            compressed_size,
+            compression_ratio,
+            metadata: serde_json::json!({ "format": "zip" }),
+        })
+    }
+
+    fn list_files(&mut self) -> Result<Vec<FileEntry>> {
+        let file = File::open(&self.path)?;
+        let mut zip = zip::ZipArchive::new(file)?;
+        let mut entries = Vec::new();
+
+        for i in 0..zip.len() {
+            let mut entry = zip.by_index(i)?;
+            let is_dir = entry.is_dir();
+            let mode = entry.unix_mode();
+            let kind = entry_kind(is_dir, mode, || {
+                let mut target = String::new();
+                entry
+                    .read_to_string(&mut target)
+                    .with_context(|| format!("Failed to read symlink target for '{}'", entry.name()))?;
+                Ok(target)
+            })?;
+
+            entries.push(FileEntry {
+                path: entry.name().trim_end_matches('/').replace('\\', "/"),
+                size: entry.size(),
+                compressed_size: entry.compressed_size(),
+                compression_method: format!("{:?}", entry.compression()),
+                modified: entry
+                    .last_modified()
+                    .and_then(|t| t.to_time().ok())
+                    .map(|t| t.unix_timestamp() as u64),
+                crc32: Some(entry.crc32()),
+                mode,
+                kind,
+                ..Default::default()
+            });
+        }
+
+        Ok(entries)
+    }
+
+    fn read_file(&mut self, path: &str) -> Result<Vec<u8>> {
+        let file = File::open(&self.path)?;
+        let mut zip = zip::ZipArchive::new(file)?;
+        let mut entry = zip
+            .by_name(path)
+            .with_context(|| format!("File '{}' not found in zip archive", path))?;
+
+        let mut buf = Vec::new();
+        entry.read_to_end(&mut buf)?;
+        Ok(buf)
+    }
+
+    fn extract(
+        &mut self,
+        output: &Path,
+        files: Option<&[String]>,
+        limits: crate::extract::ExtractLimits,
+    ) -> Result<()> {
+        let file = File::open(&self.path)?;
+        let mut zip = zip::ZipArchive::new(file)?;
+        let mut guard = crate::extract::ExtractGuard::new(output, limits)?;
+
+        for i in 0..zip.len() {
+            let mut entry = zip.by_index(i)?;
+            let is_dir = entry.is_dir();
+            let mode = entry.unix_mode();
+            let path = entry.name().trim_end_matches('/').replace('\\', "/");
+            if path.is_empty() {
+                continue;
+            }
+            if let Some(wanted) = files {
+                if !wanted.iter().any(|f| f == &path) {
+                    continue;
+                }
+            }
+
+            let size = entry.size();
+            guard.charge(size)?;
+            let output_path = guard.resolve(&path)?;
+
+            let kind = entry_kind(is_dir, mode, || {
+                let mut target = String::new();
+                entry
+                    .read_to_string(&mut target)
+                    .with_context(|| format!("Failed to read symlink target for '{}'", path))?;
+                Ok(target)
+            })?;
+
+            match &kind {
+                FileKind::Directory => {
+                    std::fs::create_dir_all(&output_path)?;
+                }
+                FileKind::Symlink { target } => {
+                    guard.validate_symlink_target(&output_path, target)?;
+                    let _ = std::fs::remove_file(&output_path);
+                    std::os::unix::fs::symlink(target, &output_path).with_context(|| {
+                        format!("Failed to create symlink: {}", output_path.display())
+                    })?;
+                }
+                _ => {
+                    let mut out_file = File::create(&output_path).with_context(|| {
+                        format!("Failed to create file: {}", output_path.display())
+                    })?;
+                    std::io::copy(&mut entry, &mut out_file)?;
+                }
+            }
+            guard.charge_written(if matches!(kind, FileKind::Directory | FileKind::Symlink { .. }) {
+                0
+            } else {
+                size
+            })?;
+
+            super::unix_meta::apply(&output_path, mode, None, None, &Default::default(), &kind)?;
+        }
+
+        Ok(())
+    }
+
+    fn verify(&mut self) -> Result<bool> {
+        let file = match File::open(&self.path) {
+            Ok(f) => f,
+            Err(_) => return Ok(false),
+        };
+
+        Ok(zip::ZipArchive::new(file).is_ok())
+    }
+
+    fn search(&mut self, pattern: &str, case_insensitive: bool) -> Result<Vec<SearchResult>> {
+        let mut results = Vec::new();
+        let file = File::open(&self.path)?;
+        let mut zip = zip::ZipArchive::new(file)?;
+        let options = crate::search::SearchOptions {
+            case_insensitive,
+            ..Default::default()
+        };
+
+        for i in 0..zip.len() {
+            let mut entry = zip.by_index(i)?;
+            if entry.is_dir() {
+                continue;
+            }
+
+            let path = entry.name().replace('\\', "/");
+            let mut buf = Vec::new();
+            if entry.read_to_end(&mut buf).is_err() {
+                continue;
+            }
+
+            let Ok(content) = String::from_utf8(buf) else {
+                continue;
+            };
+
+            for m in crate::search::find_matches(&content, pattern, &options)? {
+                results.push(SearchResult {
+                    file_path: path.clone(),
+                    line_number: m.line_number,
+                    line_content: m.line_content,
+                    match_spans: m.match_spans,
+                    before: m.before.clone(),
+                    after: m.after.clone(),
+                });
+            }
+        }
+
+        Ok(results)
+    }
+
+    fn format_name(&self) -> &'static str {
+        "Zip"
+    }
+}
+
+impl MutableArchive for ZipArchive {
+    fn write_file(&mut self, path: &str, data: &[u8]) -> Result<()> {
+        let file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .open(&self.path)
+            .with_context(|| format!("Failed to open zip archive: {}", self.path.display()))?;
+
+        // A just-created (or pre-existing empty) file isn't a valid zip to
+        // append to yet - e.g. `convert`'s destination, which `Archive::open`
+        // only records the path for rather than requiring it to exist.
+        let is_new = file.metadata().map(|m| m.len() == 0).unwrap_or(false);
+        let mut writer = if is_new {
+            zip::ZipWriter::new(file)
+        } else {
+            zip::ZipWriter::new_append(file)
+                .with_context(|| "Failed to open zip archive for appending")?
+        };
+
+        writer.start_file(path, zip::write::SimpleFileOptions::default())?;
+        std::io::Write::write_all(&mut writer, data)?;
+        writer.finish()?;
+
+        Ok(())
+    }
+
+    fn delete_file(&mut self, _path: &str) -> Result<()> {
+        anyhow::bail!("Zip archives do not support in-place deletion; rewrite the archive instead")
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        // write_file finalizes the archive on every call
+        Ok(())
+    }
+}
@@ -3,14 +3,21 @@
 //! Strategos provides unified command-line interface for managing multiple
 //! archive formats: Engram, Cartridge, DataSpool, and DataCard.
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
 use std::path::PathBuf;
 
+mod catalog;
 mod commands;
 mod crypto;
+mod extract;
 mod formats;
 mod manifest;
+mod dedup;
+mod mount;
+mod patterns;
+mod search;
+mod sql;
 mod utils;
 
 #[derive(Parser)]
@@ -65,6 +72,43 @@ enum Commands {
         /// Encrypt each file individually with password
         #[arg(long)]
         encrypt_per_file: bool,
+
+        /// Read the encryption password from this file instead of
+        /// prompting or reading STRATEGOS_PASSWORD (see crypto::password)
+        #[arg(long)]
+        password_file: Option<PathBuf>,
+
+        /// Split file content into content-defined chunks and store only
+        /// first occurrences, deduplicating across files
+        #[arg(long)]
+        dedup: bool,
+
+        /// Capture symlinks, device/fifo nodes, and each entry's mode,
+        /// ownership, and xattrs, so `extract` can recreate the tree
+        /// faithfully instead of just its regular files
+        #[arg(long)]
+        preserve_metadata: bool,
+
+        /// Add files to an existing archive at `output` instead of
+        /// creating a new one, rebuilding it in place; incompatible with
+        /// --dedup
+        #[arg(long, conflicts_with = "dedup")]
+        append: bool,
+
+        /// With --append, overwrite entries that already exist in the
+        /// archive instead of failing
+        #[arg(long, requires = "append")]
+        force: bool,
+
+        /// Print the entries that would be archived (path and size, plus a
+        /// human-readable total) without writing anything
+        #[arg(long)]
+        list: bool,
+
+        /// After packing, reopen the archive and byte-compare every entry
+        /// against the source tree, failing if anything doesn't round-trip
+        #[arg(long)]
+        verify: bool,
     },
 
     /// List files in an Engram archive
@@ -80,6 +124,11 @@ enum Commands {
         /// List only database files (.db, .sqlite)
         #[arg(short = 'd', long)]
         databases: bool,
+
+        /// Password for inspecting an encrypted archive, read from this
+        /// file instead of STRATEGOS_PASSWORD; never prompts interactively
+        #[arg(long)]
+        password_file: Option<PathBuf>,
     },
 
     /// Display metadata and statistics about an archive
@@ -99,6 +148,11 @@ enum Commands {
         /// Show manifest only
         #[arg(short, long)]
         manifest: bool,
+
+        /// Password for inspecting an encrypted archive, read from this
+        /// file instead of STRATEGOS_PASSWORD; never prompts interactively
+        #[arg(long)]
+        password_file: Option<PathBuf>,
     },
 
     /// Extract files from an Engram archive
@@ -107,17 +161,40 @@ enum Commands {
         /// Path to the Engram archive
         archive: PathBuf,
 
-        /// Output directory for extracted files
-        #[arg(short, long)]
-        output: PathBuf,
+        /// Output directory for extracted files (omit with --stdout)
+        #[arg(short, long, required_unless_present = "stdout")]
+        output: Option<PathBuf>,
 
         /// Extract only specific files
         #[arg(short, long)]
         files: Option<Vec<String>>,
 
+        /// Write the single selected entry's decompressed bytes to stdout
+        /// instead of the filesystem, for piping into other tools; requires
+        /// --files to resolve to exactly one entry
+        #[arg(long, conflicts_with = "output")]
+        stdout: bool,
+
         /// Decrypt encrypted archive with password
         #[arg(long)]
         decrypt: bool,
+
+        /// Read the decryption password from this file instead of
+        /// prompting or reading STRATEGOS_PASSWORD (see crypto::password)
+        #[arg(long)]
+        password_file: Option<PathBuf>,
+
+        /// Maximum total uncompressed bytes to write (default: 4 GiB)
+        #[arg(long, alias = "max-size")]
+        max_unpacked_size: Option<u64>,
+
+        /// Maximum number of files to write (default: 5000000)
+        #[arg(long, alias = "max-entries")]
+        max_files: Option<u64>,
+
+        /// Extract using this many worker threads instead of one
+        #[arg(short, long, default_value_t = 1)]
+        jobs: usize,
     },
 
     /// Verify archive signatures and integrity
@@ -132,6 +209,17 @@ enum Commands {
         /// Check file hashes from manifest
         #[arg(long)]
         check_hashes: bool,
+
+        /// Resolve each signature's signer key-id against keys in this
+        /// local directory (`<dir>/<key-id>.pub`) and report whether the
+        /// manifest's embedded key matches what's trusted
+        #[arg(long, conflicts_with = "keyserver")]
+        key_dir: Option<PathBuf>,
+
+        /// Resolve each signature's signer key-id against an HTTP
+        /// keyserver instead of a local directory
+        #[arg(long, conflicts_with = "key_dir")]
+        keyserver: Option<String>,
     },
 
     /// Sign an Engram archive
@@ -162,8 +250,8 @@ enum Commands {
     /// Query SQLite databases within an archive
     #[command(alias = "q")]
     Query {
-        /// Path to the Engram archive
-        archive: PathBuf,
+        /// Path to the Engram archive (omit when using --catalog)
+        archive: Option<PathBuf>,
 
         /// List all databases in archive
         #[arg(short, long)]
@@ -180,15 +268,87 @@ enum Commands {
         /// Output format: json, csv, table
         #[arg(short, long, default_value = "table")]
         format: String,
+
+        /// Catalog to select archives from, instead of querying one archive
+        #[arg(long, conflicts_with = "archive")]
+        catalog: Option<PathBuf>,
+
+        /// Glob restricting which catalog rows to select (requires --catalog)
+        #[arg(long, requires = "catalog")]
+        catalog_glob: Option<String>,
+    },
+
+    /// Convert an archive from one format to another
+    Convert {
+        /// Source archive path
+        source: PathBuf,
+
+        /// Destination archive path (format inferred from extension)
+        destination: PathBuf,
+    },
+
+    /// Import a foreign archive (tar/tar.gz/tar.xz/tar.zst/tar.bz2/zip/ar)
+    /// into a new Engram archive, detecting the source format from its
+    /// magic bytes rather than its extension
+    Import {
+        /// Source archive path
+        source: PathBuf,
+
+        /// Output archive path (defaults to input name with every
+        /// recognized extension stripped and .eng added)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Compression method for the new archive: none, lz4, zstd
+        #[arg(short, long, default_value = "lz4")]
+        compression: String,
+    },
+
+    /// Report content-defined deduplication statistics for an archive
+    Dups {
+        /// Path to the archive
+        archive: PathBuf,
+
+        /// Output format: json, csv, table
+        #[arg(short, long, default_value = "table")]
+        format: String,
+    },
+
+    /// Build a manifest catalog over a directory of Engram archives
+    CatalogBuild {
+        /// Directory containing .eng archives to scan
+        dir: PathBuf,
+
+        /// Output path for the catalog (.csv, or .db/.sqlite for a SQLite sidecar)
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+
+    /// Stream a single entry from an archive to stdout
+    Pipe {
+        /// Path to the archive
+        archive: PathBuf,
+
+        /// File path within the archive to stream
+        file: String,
+    },
+
+    /// Mount an archive as a read-only filesystem
+    Mount {
+        /// Path to the archive
+        archive: PathBuf,
+
+        /// Directory to mount the archive onto
+        mountpoint: PathBuf,
     },
 
     /// Search for text patterns in files
     Search {
-        /// Text pattern to search for
+        /// Regular expression pattern to search for
         pattern: String,
 
-        /// Path to file or archive
-        path: PathBuf,
+        /// Path to file or archive (omit when using --catalog)
+        path: Option<PathBuf>,
 
         /// Search inside archive files
         #[arg(long)]
@@ -197,6 +357,43 @@ enum Commands {
         /// Case-insensitive search
         #[arg(short, long)]
         case_insensitive: bool,
+
+        /// Match only whole words
+        #[arg(short = 'w', long)]
+        whole_word: bool,
+
+        /// Treat the pattern as a fixed string rather than a regex
+        #[arg(short = 'F', long)]
+        fixed_strings: bool,
+
+        /// Let `.` match newlines and `^`/`$` match at line boundaries, so
+        /// patterns can span multiple lines
+        #[arg(short = 'U', long)]
+        multiline: bool,
+
+        /// Print this many lines of context before each match
+        #[arg(short = 'B', long, default_value_t = 0)]
+        before: usize,
+
+        /// Print this many lines of context after each match
+        #[arg(short = 'A', long, default_value_t = 0)]
+        after: usize,
+
+        /// Stop after this many matches per file
+        #[arg(long)]
+        max_count: Option<usize>,
+
+        /// Emit structured JSON (file, line, column, match spans) instead of text
+        #[arg(long)]
+        json: bool,
+
+        /// Catalog to select archives from, instead of scanning one path
+        #[arg(long, conflicts_with = "path")]
+        catalog: Option<PathBuf>,
+
+        /// Glob restricting which catalog rows to select (requires --catalog)
+        #[arg(long, requires = "catalog")]
+        catalog_glob: Option<String>,
     },
 
     // === Cartridge-specific commands ===
@@ -250,6 +447,31 @@ enum Commands {
         /// Snapshot directory
         #[arg(short = 'd', long)]
         snapshot_dir: PathBuf,
+
+        /// Record only the delta relative to this snapshot ID, instead of a
+        /// full snapshot
+        #[arg(long)]
+        base: Option<u64>,
+
+        /// Prune down to this many most recent snapshots after creating
+        /// this one
+        #[arg(long)]
+        keep: Option<usize>,
+    },
+
+    /// Prune old Cartridge snapshots, keeping the N most recent
+    CartridgePrune {
+        /// Snapshot directory
+        #[arg(short = 'd', long)]
+        snapshot_dir: PathBuf,
+
+        /// Number of most recent snapshots to keep
+        #[arg(long)]
+        keep: usize,
+
+        /// Print what would be removed without deleting anything
+        #[arg(long)]
+        dry_run: bool,
     },
 
     // === DataSpool-specific commands ===
@@ -339,7 +561,20 @@ fn main() -> Result<()> {
             sign_key,
             encrypt,
             encrypt_per_file,
+            password_file,
+            dedup,
+            preserve_metadata,
+            append,
+            force,
+            list,
+            verify,
         } => {
+            let password = if encrypt || encrypt_per_file {
+                Some(crypto::password::resolve(password_file.as_deref(), true)?)
+            } else {
+                None
+            };
+
             commands::pack::pack(
                 &path,
                 output.as_deref(),
@@ -348,6 +583,13 @@ fn main() -> Result<()> {
                 sign_key.as_deref(),
                 encrypt,
                 encrypt_per_file,
+                password,
+                dedup,
+                preserve_metadata,
+                append,
+                force,
+                list,
+                verify,
             )?;
         }
 
@@ -355,9 +597,11 @@ fn main() -> Result<()> {
             path,
             long,
             databases,
+            password_file,
         } => {
+            let password = crypto::password::resolve_optional(password_file.as_deref())?;
             // Use new format-agnostic shared command
-            commands::shared::list(&path, long, databases)?;
+            commands::shared::list(&path, long, databases, password)?;
         }
 
         Commands::Info {
@@ -365,28 +609,63 @@ fn main() -> Result<()> {
             inspect,
             verify,
             manifest,
+            password_file,
         } => {
+            let password = crypto::password::resolve_optional(password_file.as_deref())?;
             // Use new format-agnostic shared command
-            commands::shared::info(&path, inspect, verify, manifest)?;
+            commands::shared::info(&path, inspect, verify, manifest, password)?;
         }
 
         Commands::Extract {
             archive,
             output,
             files,
-            decrypt: _decrypt,
+            stdout,
+            decrypt,
+            password_file,
+            max_unpacked_size,
+            max_files,
+            jobs,
         } => {
+            let password = if decrypt {
+                Some(crypto::password::resolve(password_file.as_deref(), false)?)
+            } else {
+                None
+            };
+            let limits = extract::ExtractLimits {
+                max_unpacked_size: max_unpacked_size
+                    .unwrap_or_else(|| extract::ExtractLimits::default().max_unpacked_size),
+                max_files: max_files.unwrap_or_else(|| extract::ExtractLimits::default().max_files),
+            };
             // Use new format-agnostic shared command
-            commands::shared::extract(&archive, &output, files)?;
+            commands::shared::extract(
+                &archive,
+                output.as_deref(),
+                files,
+                limits,
+                jobs,
+                password,
+                stdout,
+            )?;
         }
 
         Commands::Verify {
             archive,
             public_key: _public_key,
             check_hashes: _check_hashes,
+            key_dir,
+            keyserver,
         } => {
             // Use new format-agnostic shared command
             commands::shared::verify(&archive)?;
+
+            if let Some(dir) = key_dir {
+                let resolver = crypto::resolver::LocalDirectoryResolver::new(dir);
+                commands::shared::verify_signers(&archive, &resolver)?;
+            } else if let Some(base_url) = keyserver {
+                let resolver = crypto::resolver::HttpKeyserverResolver::new(base_url);
+                commands::shared::verify_signers(&archive, &resolver)?;
+            }
         }
 
         Commands::Sign {
@@ -410,14 +689,52 @@ fn main() -> Result<()> {
             database,
             sql,
             format,
-        } => {
-            commands::query::query(
-                &archive,
-                list_databases,
-                database.as_deref(),
-                sql.as_deref(),
-                &format,
-            )?;
+            catalog,
+            catalog_glob,
+        } => match catalog {
+            Some(catalog_path) => {
+                commands::catalog::query(
+                    &catalog_path,
+                    catalog_glob.as_deref(),
+                    database.as_deref(),
+                    sql.as_deref(),
+                    &format,
+                )?;
+            }
+            None => {
+                let archive = archive.context("ARCHIVE is required unless --catalog is given")?;
+                commands::query::query(
+                    &archive,
+                    list_databases,
+                    database.as_deref(),
+                    sql.as_deref(),
+                    &format,
+                )?;
+            }
+        },
+
+        Commands::Convert { source, destination } => {
+            commands::convert::convert(&source, &destination)?;
+        }
+
+        Commands::Import { source, output, compression } => {
+            commands::import::import(&source, output.as_deref(), &compression)?;
+        }
+
+        Commands::Dups { archive, format } => {
+            commands::shared::dups(&archive, &format)?;
+        }
+
+        Commands::CatalogBuild { dir, output } => {
+            commands::catalog::build(&dir, &output)?;
+        }
+
+        Commands::Pipe { archive, file } => {
+            commands::shared::pipe(&archive, &file)?;
+        }
+
+        Commands::Mount { archive, mountpoint } => {
+            commands::shared::mount(&archive, &mountpoint)?;
         }
 
         Commands::Search {
@@ -425,9 +742,36 @@ fn main() -> Result<()> {
             path,
             in_archive: _in_archive,
             case_insensitive,
+            whole_word,
+            fixed_strings,
+            multiline,
+            before,
+            after,
+            max_count,
+            json,
+            catalog,
+            catalog_glob,
         } => {
-            // Use new format-agnostic shared command
-            commands::shared::search(&path, &pattern, case_insensitive)?;
+            let options = search::SearchOptions {
+                case_insensitive,
+                whole_word,
+                fixed_strings,
+                multiline,
+                before,
+                after,
+                max_count,
+            };
+
+            match catalog {
+                Some(catalog_path) => {
+                    commands::catalog::search(&catalog_path, catalog_glob.as_deref(), &pattern, &options, json)?;
+                }
+                None => {
+                    let path = path.context("PATH is required unless --catalog is given")?;
+                    // Use new format-agnostic shared command
+                    commands::shared::search(&path, &pattern, &options, json)?;
+                }
+            }
         }
 
         // Cartridge commands
@@ -456,8 +800,18 @@ fn main() -> Result<()> {
             name,
             description,
             snapshot_dir,
+            base,
+            keep,
+        } => {
+            commands::cartridge::snapshot(&archive, name, description, &snapshot_dir, base, keep)?;
+        }
+
+        Commands::CartridgePrune {
+            snapshot_dir,
+            keep,
+            dry_run,
         } => {
-            commands::cartridge::snapshot(&archive, name, description, &snapshot_dir)?;
+            commands::cartridge::prune(&snapshot_dir, keep, dry_run)?;
         }
 
         // DataSpool commands
@@ -0,0 +1,165 @@
+//! Inode tree built lazily from an archive's flat `FileEntry` listing
+//!
+//! Archive formats store files under normalized forward-slash paths with no
+//! concept of directories; this module splits those paths into a tree so the
+//! FUSE layer can serve `lookup`/`readdir` the way a real filesystem would.
+
+use std::collections::BTreeMap;
+
+use crate::formats::FileEntry;
+
+pub const ROOT_INO: u64 = 1;
+
+#[derive(Clone)]
+pub enum InodeKind {
+    Directory,
+    File { path: String, size: u64 },
+}
+
+pub struct Inode {
+    pub kind: InodeKind,
+    children: BTreeMap<String, u64>,
+}
+
+pub struct InodeTree {
+    nodes: Vec<Inode>,
+}
+
+impl InodeTree {
+    /// Build the tree from an archive's file listing.
+    ///
+    /// Inode 0 is unused (FUSE reserves it); inode 1 is the root directory.
+    pub fn build(files: &[FileEntry]) -> Self {
+        let mut nodes = vec![
+            // placeholder for ino 0 (never addressed)
+            Inode {
+                kind: InodeKind::Directory,
+                children: BTreeMap::new(),
+            },
+            // ino 1: root
+            Inode {
+                kind: InodeKind::Directory,
+                children: BTreeMap::new(),
+            },
+        ];
+
+        for entry in files {
+            let components: Vec<&str> = entry.path.split('/').filter(|c| !c.is_empty()).collect();
+            if components.is_empty() {
+                continue;
+            }
+
+            let mut parent = ROOT_INO;
+            for (i, component) in components.iter().enumerate() {
+                let is_last = i == components.len() - 1;
+
+                if let Some(&existing) = nodes[parent as usize].children.get(*component) {
+                    parent = existing;
+                    continue;
+                }
+
+                let kind = if is_last {
+                    InodeKind::File {
+                        path: entry.path.clone(),
+                        size: entry.size,
+                    }
+                } else {
+                    InodeKind::Directory
+                };
+
+                let ino = nodes.len() as u64;
+                nodes.push(Inode {
+                    kind,
+                    children: BTreeMap::new(),
+                });
+                nodes[parent as usize]
+                    .children
+                    .insert(component.to_string(), ino);
+                parent = ino;
+            }
+        }
+
+        Self { nodes }
+    }
+
+    pub fn get(&self, ino: u64) -> Option<&Inode> {
+        self.nodes.get(ino as usize)
+    }
+
+    pub fn child(&self, parent: u64, name: &str) -> Option<u64> {
+        self.nodes.get(parent as usize)?.children.get(name).copied()
+    }
+
+    pub fn readdir(&self, ino: u64) -> Option<Vec<(u64, String, InodeKind)>> {
+        let node = self.nodes.get(ino as usize)?;
+        Some(
+            node.children
+                .iter()
+                .map(|(name, &child_ino)| {
+                    let kind = self.nodes[child_ino as usize].kind.clone();
+                    (child_ino, name.clone(), kind)
+                })
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(path: &str, size: u64) -> FileEntry {
+        FileEntry {
+            path: path.to_string(),
+            size,
+            compressed_size: size,
+            compression_method: "none".to_string(),
+            modified: None,
+            crc32: None,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_flat_files() {
+        let files = vec![entry("a.txt", 10), entry("b.txt", 20)];
+        let tree = InodeTree::build(&files);
+
+        let a_ino = tree.child(ROOT_INO, "a.txt").unwrap();
+        match &tree.get(a_ino).unwrap().kind {
+            InodeKind::File { path, size } => {
+                assert_eq!(path, "a.txt");
+                assert_eq!(*size, 10);
+            }
+            InodeKind::Directory => panic!("expected file"),
+        }
+    }
+
+    #[test]
+    fn test_nested_directories() {
+        let files = vec![entry("dir/sub/file.txt", 5)];
+        let tree = InodeTree::build(&files);
+
+        let dir_ino = tree.child(ROOT_INO, "dir").unwrap();
+        assert!(matches!(
+            tree.get(dir_ino).unwrap().kind,
+            InodeKind::Directory
+        ));
+
+        let sub_ino = tree.child(dir_ino, "sub").unwrap();
+        let file_ino = tree.child(sub_ino, "file.txt").unwrap();
+        assert!(matches!(
+            tree.get(file_ino).unwrap().kind,
+            InodeKind::File { .. }
+        ));
+    }
+
+    #[test]
+    fn test_readdir_root() {
+        let files = vec![entry("a.txt", 1), entry("dir/b.txt", 2)];
+        let tree = InodeTree::build(&files);
+
+        let entries = tree.readdir(ROOT_INO).unwrap();
+        assert_eq!(entries.len(), 2);
+    }
+}
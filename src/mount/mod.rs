@@ -0,0 +1,193 @@
+//! Read-only FUSE mount subsystem
+//!
+//! Exposes any `Archive` implementation as a read-only POSIX filesystem, so
+//! archive contents can be browsed with ordinary tools (`grep`, `cp`, an
+//! editor) without running a full `extract`.
+
+mod inode;
+
+use anyhow::{Context, Result};
+use fuser::{
+    FileAttr, FileType, Filesystem, ReplyAttr, ReplyData, ReplyDirectory, ReplyEntry, ReplyOpen,
+    Request,
+};
+use std::ffi::OsStr;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use crate::formats::Archive;
+use inode::{InodeKind, InodeTree};
+
+const TTL: Duration = Duration::from_secs(1);
+
+/// Mount an opened archive as a read-only filesystem at `mountpoint`.
+///
+/// Blocks until the filesystem is unmounted (e.g. via `umount` or Ctrl-C).
+pub fn mount(archive: Box<dyn Archive>, mountpoint: &Path) -> Result<()> {
+    let fs = ArchiveFs::new(archive)?;
+
+    fuser::mount2(fs, mountpoint, &[])
+        .with_context(|| format!("Failed to mount archive at {}", mountpoint.display()))
+}
+
+/// Most-recently-read file, cached to avoid re-decompressing on sequential reads.
+struct ReadCache {
+    ino: u64,
+    data: Vec<u8>,
+}
+
+struct ArchiveFs {
+    archive: Box<dyn Archive>,
+    tree: InodeTree,
+    cache: Option<ReadCache>,
+}
+
+impl ArchiveFs {
+    fn new(mut archive: Box<dyn Archive>) -> Result<Self> {
+        let files = archive.list_files()?;
+        let tree = InodeTree::build(&files);
+
+        Ok(Self {
+            archive,
+            tree,
+            cache: None,
+        })
+    }
+
+    fn attr_for(&self, ino: u64) -> Option<FileAttr> {
+        let node = self.tree.get(ino)?;
+
+        let (kind, size, perm) = match &node.kind {
+            InodeKind::Directory => (FileType::Directory, 0, 0o555),
+            InodeKind::File { size, .. } => (FileType::RegularFile, *size, 0o444),
+        };
+
+        Some(FileAttr {
+            ino,
+            size,
+            blocks: size.div_ceil(512),
+            atime: SystemTime::UNIX_EPOCH,
+            mtime: SystemTime::UNIX_EPOCH,
+            ctime: SystemTime::UNIX_EPOCH,
+            crtime: SystemTime::UNIX_EPOCH,
+            kind,
+            perm,
+            nlink: 1,
+            uid: 0,
+            gid: 0,
+            rdev: 0,
+            blksize: 4096,
+            flags: 0,
+        })
+    }
+}
+
+impl Filesystem for ArchiveFs {
+    fn lookup(&mut self, _req: &Request, parent: u64, name: &OsStr, reply: ReplyEntry) {
+        let Some(name) = name.to_str() else {
+            reply.error(libc::EINVAL);
+            return;
+        };
+
+        match self.tree.child(parent, name) {
+            Some(ino) => match self.attr_for(ino) {
+                Some(attr) => reply.entry(&TTL, &attr, 0),
+                None => reply.error(libc::ENOENT),
+            },
+            None => reply.error(libc::ENOENT),
+        }
+    }
+
+    fn getattr(&mut self, _req: &Request, ino: u64, _fh: Option<u64>, reply: ReplyAttr) {
+        match self.attr_for(ino) {
+            Some(attr) => reply.attr(&TTL, &attr),
+            None => reply.error(libc::ENOENT),
+        }
+    }
+
+    fn open(&mut self, _req: &Request, ino: u64, flags: i32, reply: ReplyOpen) {
+        if self.tree.get(ino).is_none() {
+            reply.error(libc::ENOENT);
+            return;
+        }
+
+        // The mount is read-only; reject anything that isn't O_RDONLY up front
+        // rather than failing later on every write attempt.
+        if flags & libc::O_ACCMODE != libc::O_RDONLY {
+            reply.error(libc::EACCES);
+            return;
+        }
+
+        reply.opened(0, 0);
+    }
+
+    fn read(
+        &mut self,
+        _req: &Request,
+        ino: u64,
+        _fh: u64,
+        offset: i64,
+        size: u32,
+        _flags: i32,
+        _lock_owner: Option<u64>,
+        reply: ReplyData,
+    ) {
+        let Some(node) = self.tree.get(ino) else {
+            reply.error(libc::ENOENT);
+            return;
+        };
+
+        let InodeKind::File { path, .. } = &node.kind else {
+            reply.error(libc::EISDIR);
+            return;
+        };
+
+        if self.cache.as_ref().map(|c| c.ino) != Some(ino) {
+            let data = match self.archive.read_file(path) {
+                Ok(data) => data,
+                Err(_) => {
+                    reply.error(libc::EIO);
+                    return;
+                }
+            };
+            self.cache = Some(ReadCache { ino, data });
+        }
+
+        let data = &self.cache.as_ref().unwrap().data;
+        let offset = offset as usize;
+        if offset >= data.len() {
+            reply.data(&[]);
+            return;
+        }
+
+        let end = (offset + size as usize).min(data.len());
+        reply.data(&data[offset..end]);
+    }
+
+    fn readdir(
+        &mut self,
+        _req: &Request,
+        ino: u64,
+        _fh: u64,
+        offset: i64,
+        mut reply: ReplyDirectory,
+    ) {
+        let Some(entries) = self.tree.readdir(ino) else {
+            reply.error(libc::ENOENT);
+            return;
+        };
+
+        for (i, (child_ino, name, kind)) in entries.into_iter().enumerate().skip(offset as usize) {
+            let file_type = match kind {
+                InodeKind::Directory => FileType::Directory,
+                InodeKind::File { .. } => FileType::RegularFile,
+            };
+
+            if reply.add(child_ino, (i + 1) as i64, file_type, name) {
+                break;
+            }
+        }
+
+        reply.ok();
+    }
+}
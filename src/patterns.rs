@@ -0,0 +1,158 @@
+//! Ordered include/exclude glob filtering for virtual archive paths
+//!
+//! Modeled on pxar's `pathpatterns`: patterns are evaluated top-to-bottom
+//! and the last one that matches a given path wins, so a later negated
+//! pattern can carve an exception out of an earlier broad include (e.g.
+//! `card_000*`, `!card_00042`). Lets callers pull "just the cards matching
+//! this prefix" out of a large archive instead of enumerating every entry.
+
+use anyhow::{Context, Result};
+
+/// One parsed include/exclude glob, in the order it should be evaluated
+#[derive(Debug, Clone)]
+pub struct MatchEntry {
+    pattern: glob::Pattern,
+    negate: bool,
+    /// Anchored patterns (a leading `/`) only match the whole path;
+    /// relative patterns also match against the final path segment, so
+    /// `card_000*` hits `card_00001` whether or not it's nested.
+    anchored: bool,
+}
+
+impl MatchEntry {
+    /// Parse one pattern line. A leading `!` negates the entry (exclude
+    /// where it would otherwise match); a leading `/` anchors it to the
+    /// whole path instead of also matching the final path segment.
+    pub fn parse(raw: &str) -> Result<Self> {
+        let (negate, raw) = match raw.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, raw),
+        };
+        let (anchored, raw) = match raw.strip_prefix('/') {
+            Some(rest) => (true, rest),
+            None => (false, raw),
+        };
+
+        let pattern =
+            glob::Pattern::new(raw).with_context(|| format!("Invalid glob pattern `{raw}`"))?;
+
+        Ok(Self {
+            pattern,
+            negate,
+            anchored,
+        })
+    }
+
+    fn matches(&self, path: &str) -> bool {
+        if self.pattern.matches(path) {
+            return true;
+        }
+        if self.anchored {
+            return false;
+        }
+        path.rsplit('/')
+            .next()
+            .is_some_and(|name| self.pattern.matches(name))
+    }
+}
+
+/// An ordered list of include/exclude patterns, evaluated top-to-bottom
+/// with last-match-wins semantics
+#[derive(Debug, Clone, Default)]
+pub struct MatchList {
+    entries: Vec<MatchEntry>,
+}
+
+impl MatchList {
+    pub fn new(entries: Vec<MatchEntry>) -> Self {
+        Self { entries }
+    }
+
+    /// Parse a `MatchList` from raw pattern strings (e.g. repeated CLI
+    /// `--pattern` arguments), in the order they should be evaluated
+    pub fn parse_all(raw: &[String]) -> Result<Self> {
+        let entries = raw
+            .iter()
+            .map(|p| MatchEntry::parse(p))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self::new(entries))
+    }
+
+    /// Whether any patterns are configured. An empty list matches
+    /// everything, so callers can skip filtering entirely as a fast path.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Whether `path` is included, given every pattern evaluated in order.
+    /// An empty list matches everything; otherwise a path that never
+    /// matches any pattern is excluded by default.
+    pub fn is_match(&self, path: &str) -> bool {
+        if self.entries.is_empty() {
+            return true;
+        }
+
+        let mut matched = false;
+        for entry in &self.entries {
+            if entry.matches(path) {
+                matched = !entry.negate;
+            }
+        }
+        matched
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_list_matches_everything() {
+        let list = MatchList::default();
+        assert!(list.is_match("card_00001"));
+    }
+
+    #[test]
+    fn simple_include_glob() {
+        let list = MatchList::parse_all(&["card_000*".to_string()]).unwrap();
+        assert!(list.is_match("card_00001"));
+        assert!(!list.is_match("card_10001"));
+    }
+
+    #[test]
+    fn negation_carves_out_exception() {
+        let list =
+            MatchList::parse_all(&["card_000*".to_string(), "!card_00042".to_string()]).unwrap();
+        assert!(list.is_match("card_00001"));
+        assert!(!list.is_match("card_00042"));
+    }
+
+    #[test]
+    fn later_pattern_wins_over_earlier() {
+        let list = MatchList::parse_all(&[
+            "!card_*".to_string(),
+            "card_00042".to_string(),
+        ])
+        .unwrap();
+        assert!(!list.is_match("card_00001"));
+        assert!(list.is_match("card_00042"));
+    }
+
+    #[test]
+    fn anchored_pattern_ignores_final_segment_match() {
+        let list = MatchList::parse_all(&["/card_00001".to_string()]).unwrap();
+        assert!(list.is_match("card_00001"));
+        assert!(!list.is_match("nested/card_00001"));
+    }
+
+    #[test]
+    fn unanchored_pattern_matches_final_segment() {
+        let list = MatchList::parse_all(&["card_00001".to_string()]).unwrap();
+        assert!(list.is_match("nested/card_00001"));
+    }
+
+    #[test]
+    fn invalid_glob_is_rejected() {
+        assert!(MatchEntry::parse("[unterminated").is_err());
+    }
+}
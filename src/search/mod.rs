@@ -0,0 +1,229 @@
+//! Shared regex-based text search engine
+//!
+//! Every `Archive::search` implementation used to hand-roll its own
+//! case-folded `contains`/`find` scan, which can't express word boundaries,
+//! alternation, or anchors. This module centralizes matching on the `regex`
+//! crate so every format shares one implementation, plus a generic
+//! multi-threaded fan-out for scanning an archive's whole file list.
+
+use anyhow::{Context, Result};
+use regex::{Regex, RegexBuilder};
+use std::path::Path;
+
+use crate::formats::{Archive, SearchResult};
+
+/// Flags controlling how a pattern is matched and how much context is shown.
+#[derive(Debug, Clone, Default)]
+pub struct SearchOptions {
+    pub case_insensitive: bool,
+    pub whole_word: bool,
+    pub fixed_strings: bool,
+    /// Let `.` match newlines and anchor `^`/`$` at line boundaries, so a
+    /// pattern can match across multiple lines.
+    pub multiline: bool,
+    pub before: usize,
+    pub after: usize,
+    pub max_count: Option<usize>,
+}
+
+/// One matching line: its number, text, every match's byte span on it, and
+/// any requested context lines.
+#[derive(Debug, Clone)]
+pub struct LineMatch {
+    pub line_number: usize,
+    pub line_content: String,
+    pub match_spans: Vec<(usize, usize)>,
+    pub before: Vec<String>,
+    pub after: Vec<String>,
+}
+
+fn build_regex(pattern: &str, opts: &SearchOptions) -> Result<Regex> {
+    let pattern = if opts.fixed_strings {
+        regex::escape(pattern)
+    } else {
+        pattern.to_string()
+    };
+    let pattern = if opts.whole_word {
+        format!(r"\b(?:{})\b", pattern)
+    } else {
+        pattern
+    };
+
+    RegexBuilder::new(&pattern)
+        .case_insensitive(opts.case_insensitive)
+        .multi_line(opts.multiline)
+        .dot_matches_new_line(opts.multiline)
+        .build()
+        .with_context(|| format!("Invalid search pattern `{}`", pattern))
+}
+
+/// Scan `content` for `pattern`, honoring `opts`. Matches are grouped by the
+/// line they start on; multiple matches on one line share a `LineMatch`.
+pub fn find_matches(content: &str, pattern: &str, opts: &SearchOptions) -> Result<Vec<LineMatch>> {
+    let regex = build_regex(pattern, opts)?;
+
+    // Split on raw bytes rather than `str::lines()`, which strips a `\r`
+    // before `\n` - if line offsets advanced by `line.len() + 1` from that
+    // stripped text, every CRLF line before a match would undercount by one
+    // byte, drifting `match_spans` columns (or even the matched line
+    // itself) further off with each preceding CRLF line.
+    let mut lines: Vec<&str> = Vec::new();
+    let mut line_starts = Vec::new();
+    let mut offset = 0usize;
+    for piece in content.split_inclusive('\n') {
+        let line = piece.strip_suffix('\n').unwrap_or(piece);
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        line_starts.push(offset);
+        lines.push(line);
+        offset += piece.len();
+    }
+
+    let mut hits: Vec<LineMatch> = Vec::new();
+
+    for m in regex.find_iter(content) {
+        if opts.max_count.is_some_and(|max| hits.len() >= max) {
+            break;
+        }
+
+        let line_idx = match line_starts.binary_search(&m.start()) {
+            Ok(i) => i,
+            Err(i) => i.saturating_sub(1),
+        };
+        let Some(&line_start) = line_starts.get(line_idx) else {
+            continue;
+        };
+        let line = lines[line_idx];
+        let span = (
+            m.start().saturating_sub(line_start),
+            m.end().saturating_sub(line_start).min(line.len()),
+        );
+
+        if let Some(last) = hits.last_mut() {
+            if last.line_number == line_idx + 1 {
+                last.match_spans.push(span);
+                continue;
+            }
+        }
+
+        let before_start = line_idx.saturating_sub(opts.before);
+        let after_end = (line_idx + 1 + opts.after).min(lines.len());
+
+        hits.push(LineMatch {
+            line_number: line_idx + 1,
+            line_content: line.to_string(),
+            match_spans: vec![span],
+            before: lines[before_start..line_idx]
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            after: lines[line_idx + 1..after_end]
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+        });
+    }
+
+    Ok(hits)
+}
+
+/// Search every file of an archive at `path`, splitting its file list
+/// across a small worker pool so large archives search quickly.
+///
+/// Each worker opens its own instance of `A` rather than sharing one across
+/// threads, since most format readers hold non-`Sync` resources (a SQLite
+/// connection, a page-store handle); re-opening from the same path is cheap
+/// next to the cost of decompressing and scanning its files.
+pub fn parallel_search<A: Archive + Send + 'static>(
+    path: &Path,
+    pattern: &str,
+    opts: &SearchOptions,
+) -> Result<Vec<SearchResult>> {
+    let files = {
+        let mut archive = A::open(path)?;
+        archive.list_files()?
+    };
+
+    if files.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let worker_count = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+        .min(files.len());
+    let chunk_size = files.len().div_ceil(worker_count).max(1);
+
+    let results: Vec<Result<Vec<SearchResult>>> = std::thread::scope(|scope| {
+        files
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || -> Result<Vec<SearchResult>> {
+                    let mut archive = A::open(path)?;
+                    let mut out = Vec::new();
+
+                    for entry in chunk {
+                        let Ok(data) = archive.read_file(&entry.path) else {
+                            continue;
+                        };
+                        let Ok(content) = String::from_utf8(data) else {
+                            continue;
+                        };
+
+                        for m in find_matches(&content, pattern, opts)? {
+                            out.push(SearchResult {
+                                file_path: entry.path.clone(),
+                                line_number: m.line_number,
+                                line_content: m.line_content,
+                                match_spans: m.match_spans,
+                                before: m.before,
+                                after: m.after,
+                            });
+                        }
+                    }
+
+                    Ok(out)
+                })
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|h| h.join().unwrap_or_else(|_| Err(anyhow::anyhow!("search worker panicked"))))
+            .collect()
+    });
+
+    let mut merged = Vec::new();
+    for result in results {
+        merged.extend(result?);
+    }
+
+    Ok(merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crlf_lines_keep_correct_line_number_and_column() {
+        let content = "short\r\nshort\r\nshort\r\nneedle here\r\n";
+        let opts = SearchOptions::default();
+
+        let hits = find_matches(content, "needle", &opts).unwrap();
+
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].line_number, 4);
+        assert_eq!(hits[0].line_content, "needle here");
+        assert_eq!(hits[0].match_spans, vec![(0, 6)]);
+    }
+
+    #[test]
+    fn lf_lines_keep_correct_line_number_and_column() {
+        let content = "short\nshort\nshort\nneedle here\n";
+        let opts = SearchOptions::default();
+
+        let hits = find_matches(content, "needle", &opts).unwrap();
+
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].line_number, 4);
+        assert_eq!(hits[0].match_spans, vec![(0, 6)]);
+    }
+}
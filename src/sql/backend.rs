@@ -0,0 +1,79 @@
+//! Materialize-into-memory fallback for opening an embedded SQLite database
+//! without a temp file
+//!
+//! The original goal here was a real SQLite VFS shim that serves page reads
+//! straight from a format's own random-access store, so `query` never has
+//! to hold a whole multi-GB database in memory at once. That's only
+//! achievable where the wrapped format crate exposes byte/page-range reads
+//! to build a VFS's `xRead` on top of in the first place:
+//!
+//! - **Engram already has it**, just not through this module. `engram_rs`
+//!   exposes its own `VfsReader`, which `EngramArchive::query` and
+//!   `commands::query` call directly - Engram's real no-materialization
+//!   path lives entirely in that crate and never touches `sql::backend`.
+//! - **Cartridge doesn't**, and can't from here. Every `self.cartridge.*`
+//!   call in `formats::cartridge` is whole-file (`read`, `write`, `list`,
+//!   `metadata`, ...); `cartridge_rs` has no byte-range or page accessor to
+//!   wire a VFS into. Building one requires an upstream addition to
+//!   `cartridge_rs`, not code in this crate.
+//!
+//! `ReadBackend`/`InMemoryBackend` below are that fallback for the second
+//! case, not a stepping stone toward a VFS that isn't possible yet. They
+//! still earn their keep: `CartridgeArchive::query` used to extract the
+//! whole database to a temp file before opening it, which doubled disk
+//! usage; deserializing the same bytes straight out of memory via
+//! `sqlite3_deserialize` at least drops the temp-file copy, even though the
+//! database is still fully resident in memory either way.
+
+use anyhow::{Context, Result};
+use rusqlite::{Connection, DatabaseName};
+
+/// Source of the raw bytes behind an embedded SQLite database, for formats
+/// with no byte-range reader to back a real VFS with (see the module docs)
+pub trait ReadBackend {
+    /// Return the full database contents
+    ///
+    /// Backends that hold the bytes in memory already should consume
+    /// themselves rather than clone; callers only need the result once.
+    fn read_all(&mut self) -> Result<Vec<u8>>;
+}
+
+/// The only [`ReadBackend`] that exists today: a plain in-memory buffer,
+/// for formats whose crate only exposes whole-file reads (currently
+/// Cartridge - see the module docs for why this can't be upgraded to a
+/// real VFS without an upstream `cartridge_rs` change)
+pub struct InMemoryBackend {
+    data: Vec<u8>,
+}
+
+impl InMemoryBackend {
+    pub fn new(data: Vec<u8>) -> Self {
+        Self { data }
+    }
+}
+
+impl ReadBackend for InMemoryBackend {
+    fn read_all(&mut self) -> Result<Vec<u8>> {
+        Ok(std::mem::take(&mut self.data))
+    }
+}
+
+/// Open `backend`'s bytes as an in-process SQLite database, no temp file
+///
+/// This hands the buffer to SQLite's `sqlite3_deserialize` (via
+/// `Connection::deserialize`, requires rusqlite's `modern_sqlite` feature)
+/// so the database lives entirely in memory for the lifetime of the
+/// connection. This is strictly the full-materialization fallback (see the
+/// module docs) - it does not give `query` random-access, page-level reads
+/// against a multi-GB database; only Engram has that today, via
+/// `engram_rs::VfsReader`, which bypasses this function entirely.
+pub fn open_database(mut backend: impl ReadBackend) -> Result<Connection> {
+    let data = backend.read_all()?;
+
+    let conn = Connection::open_in_memory()
+        .context("Failed to open in-memory SQLite connection")?;
+    conn.deserialize(DatabaseName::Main, data, None)
+        .context("Failed to attach database bytes via sqlite3_deserialize")?;
+
+    Ok(conn)
+}
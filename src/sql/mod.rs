@@ -0,0 +1,9 @@
+//! SQLite access helpers shared by the `QueryableArchive` implementations
+//!
+//! There's no `sql::vfs` module here: a real SQLite VFS needs a format
+//! crate that exposes byte/page-range reads to back it with, which only
+//! `engram_rs` does today (via its own `VfsReader`, used directly by
+//! `EngramArchive`/`commands::query`). `backend` is the honest fallback for
+//! formats that don't - see its module docs for why Cartridge is stuck
+//! there until `cartridge_rs` grows one.
+pub mod backend;
@@ -0,0 +1,17 @@
+//! Compression utilities
+
+use anyhow::Result;
+use engram_rs::CompressionMethod;
+
+/// Parse compression method from string
+pub fn parse_compression(s: &str) -> Result<CompressionMethod> {
+    match s.to_lowercase().as_str() {
+        "none" => Ok(CompressionMethod::None),
+        "lz4" => Ok(CompressionMethod::Lz4),
+        "zstd" => Ok(CompressionMethod::Zstd),
+        _ => Err(anyhow::anyhow!(
+            "Invalid compression method: '{}'. Use: none, lz4, zstd",
+            s
+        )),
+    }
+}